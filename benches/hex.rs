@@ -5,7 +5,7 @@ use rustc_hex::{FromHex, ToHex};
 fn encode(criterion: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let mut group = criterion.benchmark_group("encode");
-    for size in &[32usize, 1024, 65536] {
+    for size in &[20usize, 32, 1024, 65536] {
         let mut data = vec![0u8; *size];
         rng.fill_bytes(&mut data);
 
@@ -61,7 +61,7 @@ fn decode(criterion: &mut Criterion) {
     let mut rng = rand::thread_rng();
     let mut group = criterion.benchmark_group("decode");
 
-    for size in &[32usize, 1024, 65536] {
+    for size in &[20usize, 32, 1024, 65536] {
         let mut data = vec![0u8; *size];
         rng.fill_bytes(&mut data);
         let hex_data = faster_hex::hex_string(&data).unwrap();
@@ -74,6 +74,28 @@ fn decode(criterion: &mut Criterion) {
             },
         );
 
+        group.bench_with_input(
+            format!("hex in_place [{} bytes]", size),
+            &hex_data,
+            |bencher, hex_data| {
+                bencher.iter_batched(
+                    || hex_data.clone().into_bytes(),
+                    |hex_data| criterion::black_box(hex::decode_in_place(hex_data).unwrap()),
+                    criterion::BatchSize::SmallInput,
+                )
+            },
+        );
+
+        group.bench_with_input(
+            format!("hex lower_unchecked_case [{} bytes]", size),
+            &hex_data,
+            |bencher, hex_data| {
+                bencher.iter(|| {
+                    criterion::black_box(hex::decode_lower_unchecked_case(hex_data).unwrap())
+                })
+            },
+        );
+
         group.bench_with_input(
             format!("rustc_hex [{} bytes]", size),
             &hex_data,
@@ -126,5 +148,186 @@ fn decode(criterion: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, encode, decode);
+// Compares `encode()`, which writes bytes into the string's buffer, with
+// collecting the `char`s produced by `ToHex`.
+fn encode_string(criterion: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut group = criterion.benchmark_group("encode_string");
+
+    for size in &[20usize, 1024, 65536] {
+        let mut data = vec![0u8; *size];
+        rng.fill_bytes(&mut data);
+
+        group.bench_with_input(format!("bytes [{} bytes]", size), &data, |bencher, data| {
+            bencher.iter(|| criterion::black_box(hex::encode(data)))
+        });
+
+        group.bench_with_input(format!("chars [{} bytes]", size), &data, |bencher, data| {
+            bencher.iter(|| criterion::black_box(hex::ToHex::encode_hex::<String>(data)))
+        });
+    }
+}
+
+// The slice based benchmarks write into preallocated buffers, which separates
+// the conversion's throughput from the allocator's overhead.
+fn encode_to_slice(criterion: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut group = criterion.benchmark_group("encode_to_slice");
+
+    for size in &[32usize, 1024, 65536] {
+        let mut data = vec![0u8; *size];
+        rng.fill_bytes(&mut data);
+        let mut output = vec![0u8; size * 2];
+
+        group.bench_function(format!("hex [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                hex::encode_to_slice(&data, &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("faster_hex [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                faster_hex::hex_encode(&data, &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("base16 [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                base16::encode_config_slice(&data, base16::EncodeLower, &mut output);
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("binascii [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                binascii::bin2hex(&data, &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+    }
+}
+
+fn decode_to_slice(criterion: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut group = criterion.benchmark_group("decode_to_slice");
+
+    for size in &[32usize, 1024, 65536] {
+        let mut data = vec![0u8; *size];
+        rng.fill_bytes(&mut data);
+        let hex_data = faster_hex::hex_string(&data).unwrap();
+        let mut output = vec![0u8; *size];
+
+        group.bench_function(format!("hex [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                hex::decode_to_slice(&hex_data, &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("faster_hex [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                faster_hex::hex_decode(hex_data.as_bytes(), &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("base16 [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                base16::decode_slice(&hex_data, &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+
+        group.bench_function(format!("binascii [{} bytes]", size), |bencher| {
+            bencher.iter(|| {
+                binascii::hex2bin(hex_data.as_bytes(), &mut output).unwrap();
+                criterion::black_box(&output);
+            })
+        });
+    }
+}
+
+// Compares the NEON path against `faster_hex` on slices, which avoids measuring
+// allocations.
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+fn simd(criterion: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut group = criterion.benchmark_group("simd");
+
+    for size in &[32usize, 1024, 65536] {
+        let mut data = vec![0u8; *size];
+        rng.fill_bytes(&mut data);
+        let hex_data = hex::encode(&data);
+
+        group.bench_with_input(
+            format!("encode neon [{} bytes]", size),
+            &data,
+            |bencher, data| {
+                let mut output = vec![0; size * 2];
+                bencher.iter(|| {
+                    hex::encode_to_slice(data, &mut output).unwrap();
+                    criterion::black_box(&output);
+                })
+            },
+        );
+
+        group.bench_with_input(
+            format!("encode faster_hex [{} bytes]", size),
+            &data,
+            |bencher, data| {
+                let mut output = vec![0; size * 2];
+                bencher.iter(|| {
+                    faster_hex::hex_encode(data, &mut output).unwrap();
+                    criterion::black_box(&output);
+                })
+            },
+        );
+
+        group.bench_with_input(
+            format!("decode neon [{} bytes]", size),
+            &hex_data,
+            |bencher, hex_data| {
+                let mut output = vec![0; *size];
+                bencher.iter(|| {
+                    hex::decode_to_slice(hex_data, &mut output).unwrap();
+                    criterion::black_box(&output);
+                })
+            },
+        );
+
+        group.bench_with_input(
+            format!("decode faster_hex [{} bytes]", size),
+            &hex_data,
+            |bencher, hex_data| {
+                let mut output = vec![0; *size];
+                bencher.iter(|| {
+                    faster_hex::hex_decode(hex_data.as_bytes(), &mut output).unwrap();
+                    criterion::black_box(&output);
+                })
+            },
+        );
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+criterion_group!(
+    benches,
+    encode,
+    decode,
+    encode_string,
+    encode_to_slice,
+    decode_to_slice,
+    simd
+);
+#[cfg(not(all(feature = "simd", target_arch = "aarch64")))]
+criterion_group!(
+    benches,
+    encode,
+    decode,
+    encode_string,
+    encode_to_slice,
+    decode_to_slice
+);
 criterion_main!(benches);
@@ -0,0 +1,63 @@
+#![no_main]
+use hex::{FromHexError, HexConfig};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let decoded = hex::decode(data);
+
+    // Successful decodes round-trip, apart from the case of the letters.
+    if let Ok(bytes) = &decoded {
+        assert_eq!(
+            hex::encode(bytes).as_bytes(),
+            &data.to_ascii_lowercase()[..]
+        );
+    }
+
+    // All strict decoders agree with `decode`.
+    let mut out = vec![0; data.len() / 2];
+    assert_eq!(
+        hex::decode_to_slice(data, &mut out).map(|()| out),
+        decoded.clone()
+    );
+    assert_eq!(hex::decode_ct(data), decoded);
+    assert_eq!(hex::decode_limited(data, usize::MAX), decoded);
+    if data.len() % 2 == 0 {
+        assert_eq!(
+            hex::decode_iter(data).collect::<Result<Vec<_>, _>>(),
+            decoded
+        );
+        assert_eq!(hex::decode_chunks(data.chunks(3)), decoded);
+    }
+    match hex::decode_collect_errors(data) {
+        Ok(bytes) => assert_eq!(Ok(bytes), decoded),
+        Err(errors) => assert!(!errors.is_empty() && decoded.is_err()),
+    }
+    if let Ok(s) = std::str::from_utf8(data) {
+        if s.is_ascii() {
+            assert_eq!(hex::decode_str(s), decoded);
+        } else {
+            assert!(hex::decode_str(s).is_err());
+        }
+    }
+
+    // Tolerant decoders mustn't panic, and agree with `decode` on valid input.
+    let nibbles = hex::decode_nibbles(data);
+    let leading_zero = hex::decode_odd_as_leading_zero(data);
+    let skipping = hex::decode_skipping(data, |c| c.is_ascii_whitespace() || c == b':');
+    let underscored = hex::decode_underscored(data);
+    let config = HexConfig::new()
+        .prefix(true)
+        .separator(':', 1)
+        .ignore_whitespace(true)
+        .decode(data);
+    if let Ok(bytes) = decoded {
+        assert_eq!(nibbles, Ok((bytes.clone(), false)));
+        assert_eq!(leading_zero, Ok(bytes.clone()));
+        assert_eq!(skipping, Ok(bytes.clone()));
+        assert_eq!(underscored, Ok(bytes.clone()));
+        assert_eq!(config, Ok(bytes));
+    }
+    if let Err(FromHexError::InvalidHexCharacter { index, .. }) = nibbles {
+        assert!(index < data.len());
+    }
+});
@@ -0,0 +1,225 @@
+//! A builder combining the encoding and decoding options.
+
+use crate::{byte2hex, val, Case, FromHexError};
+use alloc::{string::String, vec::Vec};
+
+/// Configurable hex encoding and decoding.
+///
+/// The free functions such as `encode()` and `decode()` cover the common
+/// case. If several options need to be combined, e.g. a `0x` prefix and
+/// separators between groups of bytes, build a `HexConfig` instead.
+///
+/// # Example
+///
+/// ```
+/// use hex::HexConfig;
+///
+/// let config = HexConfig::new().uppercase().prefix(true).separator(':', 2);
+/// assert_eq!(config.encode([0xde, 0xad, 0xbe, 0xef]), "0xDEAD:BEEF");
+/// assert_eq!(config.decode("0xdead:beef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexConfig {
+    case: Case,
+    prefix: bool,
+    separator: Option<(char, usize)>,
+    ignore_whitespace: bool,
+}
+
+impl HexConfig {
+    /// Creates a config which encodes lowercase hex without prefix or
+    /// separators and decodes strictly, just like `encode()` and `decode()`.
+    pub fn new() -> Self {
+        Self {
+            case: Case::Lower,
+            prefix: false,
+            separator: None,
+            ignore_whitespace: false,
+        }
+    }
+
+    /// Encodes using lowercase characters (e.g. `f9b4ca`). This is the
+    /// default.
+    pub fn lowercase(self) -> Self {
+        self.case(Case::Lower)
+    }
+
+    /// Encodes using uppercase characters (e.g. `F9B4CA`).
+    pub fn uppercase(self) -> Self {
+        self.case(Case::Upper)
+    }
+
+    /// Encodes using characters of the given case.
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Whether encoding prepends `0x`, and decoding accepts a leading `0x` or
+    /// `0X`.
+    pub fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Encodes with `separator` between every `group` bytes, and makes
+    /// decoding skip any occurrence of `separator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is `0`.
+    #[track_caller]
+    pub fn separator(mut self, separator: char, group: usize) -> Self {
+        assert!(group > 0, "separator group size must be non-zero");
+        self.separator = Some((separator, group));
+        self
+    }
+
+    /// Whether decoding skips ASCII whitespace.
+    pub fn ignore_whitespace(mut self, ignore_whitespace: bool) -> Self {
+        self.ignore_whitespace = ignore_whitespace;
+        self
+    }
+
+    /// Encodes `data` as hex string according to this config.
+    pub fn encode<T: AsRef<[u8]>>(&self, data: T) -> String {
+        let data = data.as_ref();
+        let table = self.case.table();
+
+        let mut out = String::with_capacity(2 + data.len() * 3);
+        if self.prefix {
+            out.push_str("0x");
+        }
+        for (i, byte) in data.iter().enumerate() {
+            if let Some((separator, group)) = self.separator {
+                if i > 0 && i % group == 0 {
+                    out.push(separator);
+                }
+            }
+            let (high, low) = byte2hex(*byte, table);
+            out.push(high as char);
+            out.push(low as char);
+        }
+
+        out
+    }
+
+    /// Decodes a hex string according to this config.
+    ///
+    /// Both, upper and lower case characters are valid regardless of the
+    /// configured case. Indices in errors refer to positions in `data`,
+    /// including any prefix, separators and whitespace.
+    pub fn decode<T: AsRef<[u8]>>(&self, data: T) -> Result<Vec<u8>, FromHexError> {
+        let data = data.as_ref();
+        let mut separator_buffer = [0; 4];
+        let separator = self
+            .separator
+            .map(|(separator, _)| separator.encode_utf8(&mut separator_buffer).as_bytes());
+
+        let mut index = 0;
+        if self.prefix && (data.starts_with(b"0x") || data.starts_with(b"0X")) {
+            index = 2;
+        }
+
+        let mut out = Vec::with_capacity(data.len() / 2);
+        let mut high = None;
+        while index < data.len() {
+            if let Some(separator) = separator {
+                if data[index..].starts_with(separator) {
+                    index += separator.len();
+                    continue;
+                }
+            }
+            if self.ignore_whitespace && data[index].is_ascii_whitespace() {
+                index += 1;
+                continue;
+            }
+
+            let value = val(data[index], index)?;
+            match high.take() {
+                Some(high) => out.push(high << 4 | value),
+                None => high = Some(value),
+            }
+            index += 1;
+        }
+
+        if high.is_some() {
+            return Err(FromHexError::OddLength);
+        }
+
+        Ok(out)
+    }
+}
+
+impl Default for HexConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let config = HexConfig::new();
+        assert_eq!(config.encode("foobar"), "666f6f626172");
+        assert_eq!(config.decode("666F6f626172").unwrap(), b"foobar");
+        assert_eq!(
+            config.decode("0x66"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 })
+        );
+        assert_eq!(
+            config.decode("66 6f"),
+            Err(FromHexError::InvalidHexCharacter { c: ' ', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_encode_prefix_separator() {
+        let config = HexConfig::new().prefix(true).separator(' ', 2);
+        assert_eq!(config.encode("foobar"), "0x666f 6f62 6172");
+        assert_eq!(config.encode("fooba"), "0x666f 6f62 61");
+        assert_eq!(config.encode(""), "0x");
+
+        let config = config.uppercase().separator('·', 1);
+        assert_eq!(config.encode([0xab, 0xcd]), "0xAB·CD");
+        assert_eq!(config.lowercase().encode([0xab, 0xcd]), "0xab·cd");
+        assert_eq!(config.case(Case::Upper), config);
+    }
+
+    #[test]
+    fn test_decode_prefix_separator() {
+        let config = HexConfig::new().prefix(true).separator('·', 1);
+        assert_eq!(config.decode("0xAB·cd").unwrap(), [0xab, 0xcd]);
+        assert_eq!(config.decode("0Xab·cd").unwrap(), [0xab, 0xcd]);
+        assert_eq!(config.decode("a·b·cd").unwrap(), [0xab, 0xcd]);
+        assert_eq!(
+            config.decode("0xab:cd"),
+            Err(FromHexError::InvalidHexCharacter { c: ':', index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_decode_whitespace_prefix() {
+        let config = HexConfig::new().prefix(true).ignore_whitespace(true);
+        assert_eq!(config.decode("0x66 6f\n6f\t").unwrap(), b"foo");
+        assert_eq!(config.decode("  ").unwrap(), b"");
+        assert_eq!(config.decode("0x66 6"), Err(FromHexError::OddLength));
+        assert_eq!(
+            config.decode(" 0x66"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+        assert_eq!(
+            config.decode("0x66 6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 6 })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_separator_zero_group() {
+        let _ = HexConfig::new().separator(':', 0);
+    }
+}
@@ -0,0 +1,163 @@
+//! Hex strings followed by a CRC32 checksum of the data.
+
+use crate::{decode, encode, FromHexError};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// The number of bytes of the checksum.
+const CRC_LEN: usize = 4;
+
+/// The error type for decoding a hex string with a CRC32 checksum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecodeCrcError {
+    /// The input isn't a valid hex string.
+    Hex(FromHexError),
+
+    /// The input decodes to fewer than the 4 bytes of the checksum.
+    MissingChecksum,
+
+    /// The checksum doesn't match the data, which is likely corrupted.
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl From<FromHexError> for DecodeCrcError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeCrcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Hex(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DecodeCrcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Hex(error) => write!(f, "{}", error),
+            Self::MissingChecksum => write!(f, "Input too short to contain a checksum"),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum mismatch: expected {:08x}, computed {:08x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+/// Encodes `data` as hex string using lowercase characters, followed by its
+/// CRC32 checksum.
+///
+/// The checksum is appended in big endian byte order before encoding, so
+/// `decode_with_crc32()` reverses this.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encode_with_crc32("kiwi"), "6b697769de75697c");
+/// ```
+#[must_use]
+pub fn encode_with_crc32<T: AsRef<[u8]>>(data: T) -> String {
+    let data = data.as_ref();
+    let mut checksummed = Vec::with_capacity(data.len() + CRC_LEN);
+    checksummed.extend_from_slice(data);
+    checksummed.extend_from_slice(&crc32fast::hash(data).to_be_bytes());
+    encode(checksummed)
+}
+
+/// Decodes a hex string into raw bytes, verifying the CRC32 checksum in the
+/// last 4 bytes.
+///
+/// The checksum is stored in big endian byte order and covers the bytes
+/// preceding it. On success, these bytes are returned without the checksum.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_with_crc32("6b697769de75697c"), Ok(b"kiwi".to_vec()));
+/// assert!(hex::decode_with_crc32("6b697769de75697d").is_err());
+/// ```
+pub fn decode_with_crc32<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, DecodeCrcError> {
+    let mut decoded = decode(data)?;
+    let payload_len = decoded
+        .len()
+        .checked_sub(CRC_LEN)
+        .ok_or(DecodeCrcError::MissingChecksum)?;
+
+    let mut checksum = [0; CRC_LEN];
+    checksum.copy_from_slice(&decoded[payload_len..]);
+    let expected = u32::from_be_bytes(checksum);
+    let actual = crc32fast::hash(&decoded[..payload_len]);
+    if expected != actual {
+        return Err(DecodeCrcError::ChecksumMismatch { expected, actual });
+    }
+
+    decoded.truncate(payload_len);
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_with_crc32() {
+        // The CRC32 check value of "123456789".
+        assert_eq!(
+            decode_with_crc32("313233343536373839cbf43926"),
+            Ok(b"123456789".to_vec())
+        );
+        assert_eq!(decode_with_crc32("00000000"), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_encode_with_crc32() {
+        assert_eq!(encode_with_crc32("123456789"), "313233343536373839cbf43926");
+        assert_eq!(encode_with_crc32(""), "00000000");
+    }
+
+    #[test]
+    fn test_crc32_roundtrip() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..data.len() {
+            let encoded = encode_with_crc32(&data[..len]);
+            assert_eq!(decode_with_crc32(&encoded), Ok(data[..len].to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_decode_with_crc32_corrupted() {
+        assert_eq!(
+            decode_with_crc32("313233343536373838cbf43926"),
+            Err(DecodeCrcError::ChecksumMismatch {
+                expected: 0xcbf43926,
+                actual: crc32fast::hash(b"123456788")
+            })
+        );
+        assert_eq!(
+            decode_with_crc32("313233343536373839cbf43927"),
+            Err(DecodeCrcError::ChecksumMismatch {
+                expected: 0xcbf43927,
+                actual: 0xcbf43926
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_with_crc32_errors() {
+        assert_eq!(
+            decode_with_crc32("cbf439"),
+            Err(DecodeCrcError::MissingChecksum)
+        );
+        assert_eq!(decode_with_crc32(""), Err(DecodeCrcError::MissingChecksum));
+        assert_eq!(
+            decode_with_crc32("cbf4392"),
+            Err(DecodeCrcError::Hex(FromHexError::OddLength))
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Hex dumps in the canonical layout of `hexdump -C`.
+
+use crate::{val, HEX_PAIRS_LOWER};
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// The number of bytes per line of `hexdump()`.
+const BYTES_PER_LINE: usize = 16;
+
+/// The number of bytes after which an extra space is inserted.
+const GROUP_SIZE: usize = 8;
+
+/// Formats `data` as hex dump with 16 bytes per line, like `hexdump -C`.
+///
+/// Each line starts with the offset of its first byte, followed by the bytes
+/// in hex, with an extra space after every eight, and the printable ASCII
+/// characters between `|`s, showing others as `.`. A final line holds the
+/// total length. Unlike `hexdump`, repeated lines aren't collapsed.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(
+///     hex::hexdump("Hello world!\n"),
+///     "00000000  48 65 6c 6c 6f 20 77 6f  72 6c 64 21 0a           |Hello world!.|\n\
+///      0000000d\n"
+/// );
+/// ```
+#[must_use]
+pub fn hexdump<T: AsRef<[u8]>>(data: T) -> String {
+    hexdump_with(data, BYTES_PER_LINE)
+}
+
+/// Formats `data` as hex dump with `bytes_per_line` bytes per line.
+///
+/// Apart from the line length, this works exactly like `hexdump()`.
+///
+/// # Panics
+///
+/// Panics if `bytes_per_line` is `0`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(
+///     hex::hexdump_with("Hello", 4),
+///     "00000000  48 65 6c 6c  |Hell|\n\
+///      00000004  6f           |o|\n\
+///      00000005\n"
+/// );
+/// ```
+#[track_caller]
+#[must_use]
+pub fn hexdump_with<T: AsRef<[u8]>>(data: T, bytes_per_line: usize) -> String {
+    assert!(bytes_per_line > 0, "bytes per line must be non-zero");
+
+    let data = data.as_ref();
+    let mut dump = String::new();
+    for (i, line) in data.chunks(bytes_per_line).enumerate() {
+        write!(dump, "{:08x}  ", i * bytes_per_line).expect("writing to a String can't fail");
+        for column in 0..bytes_per_line {
+            match line.get(column) {
+                Some(&byte) => {
+                    let [high, low] = HEX_PAIRS_LOWER[byte as usize];
+                    dump.push(high as char);
+                    dump.push(low as char);
+                    dump.push(' ');
+                }
+                None => dump.push_str("   "),
+            }
+            if column % GROUP_SIZE == GROUP_SIZE - 1 && column + 1 < bytes_per_line {
+                dump.push(' ');
+            }
+        }
+
+        dump.push_str(" |");
+        for &byte in line {
+            let printable = byte.is_ascii_graphic() || byte == b' ';
+            dump.push(if printable { byte as char } else { '.' });
+        }
+        dump.push_str("|\n");
+    }
+
+    if !data.is_empty() {
+        writeln!(dump, "{:08x}", data.len()).expect("writing to a String can't fail");
+    }
+    dump
+}
+
+/// The error type for parsing a hex dump, pointing out the malformed content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseDumpError {
+    /// The line of the malformed content, starting at 1.
+    pub line: usize,
+
+    /// The position of the malformed content within its line in bytes,
+    /// starting at 1. Offsets which don't match the bytes before them are
+    /// reported at column 1.
+    pub column: usize,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDumpError {}
+
+impl fmt::Display for ParseDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Malformed hex dump at line {}, column {}",
+            self.line, self.column
+        )
+    }
+}
+
+/// Parses a hex dump as printed by `hexdump -C`, `xxd` or `hexdump()` back
+/// into the dumped bytes.
+///
+/// Each line has to start with the offset of its first byte in hex. If the
+/// offset is followed by `:`, the line is taken to be from `xxd`, and the
+/// bytes end at the first two consecutive spaces. Otherwise, they end at the
+/// first `|`. Either way, the ASCII column is ignored. The bytes are given
+/// as hex digits, with any number of bytes per group, separated by spaces.
+/// Empty lines and lines holding nothing but an offset, such as the final
+/// total length, are skipped. A line consisting of `*` stands for repetitions
+/// of the preceding line, up to the offset of the next one. An offset which
+/// isn't reached by a whole number of repetitions is rejected, and so is one
+/// which can't be allocated. As a short dump can still expand to a large
+/// output this way, use `parse_hexdump_limited()` for untrusted input.
+///
+/// # Example
+///
+/// ```
+/// let dump = "00000000  48 65 6c 6c 6f 20 77 6f  72 6c 64 21 0a           |Hello world!.|\n\
+///             0000000d\n";
+/// assert_eq!(hex::parse_hexdump(dump), Ok(b"Hello world!\n".to_vec()));
+///
+/// let dump = "00000000: 4865 6c6c 6f0a  Hello.";
+/// assert_eq!(hex::parse_hexdump(dump), Ok(b"Hello\n".to_vec()));
+/// ```
+pub fn parse_hexdump<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, ParseDumpError> {
+    parse_hexdump_limited(data, usize::MAX)
+}
+
+/// Parses a hex dump like `parse_hexdump()`, rejecting it if it would expand
+/// to more than `max_bytes` bytes.
+///
+/// The error points at the line which would exceed the limit, at column 1 for
+/// the offset after a `*` line, or else at the first byte over the limit.
+///
+/// # Example
+///
+/// ```
+/// let dump = "00000000  00 00 00 00\n*\n00100000\n";
+/// assert_eq!(hex::parse_hexdump_limited(dump, 1 << 20), Ok(vec![0; 1 << 20]));
+/// assert_eq!(
+///     hex::parse_hexdump_limited(dump, 1024),
+///     Err(hex::ParseDumpError { line: 3, column: 1 })
+/// );
+/// ```
+pub fn parse_hexdump_limited<T: AsRef<[u8]>>(
+    data: T,
+    max_bytes: usize,
+) -> Result<Vec<u8>, ParseDumpError> {
+    let mut out = Vec::new();
+    let mut previous = 0..0;
+    let mut repeat = None;
+
+    for (i, line) in data.as_ref().split(|&c| c == b'\n').enumerate() {
+        let error = |column: usize| ParseDumpError {
+            line: i + 1,
+            column: column + 1,
+        };
+
+        let end = line
+            .iter()
+            .rposition(|c| !c.is_ascii_whitespace())
+            .map_or(0, |last| last + 1);
+        let line = &line[..end];
+        if line.is_empty() {
+            continue;
+        }
+        if line == b"*" {
+            repeat = Some(i);
+            continue;
+        }
+
+        let offset_len = line
+            .iter()
+            .position(|c| !c.is_ascii_hexdigit())
+            .unwrap_or(line.len());
+        let offset = parse_offset(&line[..offset_len]).ok_or_else(|| error(0))?;
+
+        if repeat.take().is_some() && !previous.is_empty() {
+            let gap = offset.checked_sub(out.len()).ok_or_else(|| error(0))?;
+            if gap % previous.len() != 0 || offset > max_bytes {
+                return Err(error(0));
+            }
+            out.try_reserve(gap).map_err(|_| error(0))?;
+            while out.len() < offset {
+                out.extend_from_within(previous.clone());
+            }
+        }
+        if offset != out.len() {
+            return Err(error(0));
+        }
+
+        let (start, bytes) = match line[offset_len..].strip_prefix(b":") {
+            Some(rest) => {
+                let end = rest.windows(2).position(|pair| pair == b"  ");
+                (offset_len + 1, &rest[..end.unwrap_or(rest.len())])
+            }
+            None => {
+                let rest = &line[offset_len..];
+                let end = rest.iter().position(|&c| c == b'|');
+                (offset_len, &rest[..end.unwrap_or(rest.len())])
+            }
+        };
+
+        let line_start = out.len();
+        let mut high = None;
+        for (column, &c) in bytes.iter().enumerate() {
+            let column = start + column;
+            if c == b' ' {
+                if let Some((_, column)) = high {
+                    return Err(error(column));
+                }
+                continue;
+            }
+
+            let value = val(c, column).map_err(|_| error(column))?;
+            match high.take() {
+                Some((_, column)) if out.len() == max_bytes => return Err(error(column)),
+                Some((high, _)) => out.push(high << 4 | value),
+                None => high = Some((value, column)),
+            }
+        }
+        if let Some((_, column)) = high {
+            return Err(error(column));
+        }
+
+        if out.len() > line_start {
+            previous = line_start..out.len();
+        }
+    }
+
+    match repeat {
+        Some(line) => Err(ParseDumpError {
+            line: line + 1,
+            column: 1,
+        }),
+        None => Ok(out),
+    }
+}
+
+/// Parses the hex digits of an offset, or returns `None` if there are none or
+/// the value overflows.
+fn parse_offset(digits: &[u8]) -> Option<usize> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    digits.iter().try_fold(0usize, |offset, &c| {
+        let value = val(c, 0).ok()?;
+        offset.checked_mul(16)?.checked_add(value.into())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hexdump() {
+        let data: Vec<u8> = b"The quick brown fox jumps\x00\x01\xff\x7f".to_vec();
+        assert_eq!(
+            hexdump(&data),
+            "00000000  54 68 65 20 71 75 69 63  6b 20 62 72 6f 77 6e 20  |The quick brown |\n\
+             00000010  66 6f 78 20 6a 75 6d 70  73 00 01 ff 7f           |fox jumps....|\n\
+             0000001d\n"
+        );
+    }
+
+    #[test]
+    fn test_hexdump_full_lines() {
+        let data: Vec<u8> = (0..32).collect();
+        assert_eq!(
+            hexdump(&data),
+            "00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|\n\
+             00000010  10 11 12 13 14 15 16 17  18 19 1a 1b 1c 1d 1e 1f  |................|\n\
+             00000020\n"
+        );
+    }
+
+    #[test]
+    fn test_hexdump_with() {
+        assert_eq!(
+            hexdump_with("Hello world!", 8),
+            "00000000  48 65 6c 6c 6f 20 77 6f  |Hello wo|\n\
+             00000008  72 6c 64 21              |rld!|\n\
+             0000000c\n"
+        );
+        assert_eq!(
+            hexdump_with("abc", 1),
+            "00000000  61  |a|\n00000001  62  |b|\n00000002  63  |c|\n00000003\n"
+        );
+    }
+
+    #[test]
+    fn test_hexdump_empty() {
+        assert_eq!(hexdump(""), "");
+        assert_eq!(hexdump_with("", 4), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "bytes per line must be non-zero")]
+    fn test_hexdump_with_zero() {
+        let _ = hexdump_with("abc", 0);
+    }
+
+    #[test]
+    fn test_parse_hexdump_roundtrip() {
+        let data: Vec<u8> = (0..=255).chain(b"Hello world!".iter().copied()).collect();
+        for len in &[0, 1, 7, 8, 15, 16, 17, 255, data.len()] {
+            let data = &data[..*len];
+            assert_eq!(parse_hexdump(hexdump(data)), Ok(data.to_vec()));
+            for bytes_per_line in &[1, 3, 8, 20] {
+                assert_eq!(
+                    parse_hexdump(hexdump_with(data, *bytes_per_line)),
+                    Ok(data.to_vec())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_hexdump_gutter() {
+        // The ASCII column may contain anything, including hex digits and
+        // the delimiters.
+        let data = b"cafe | 00  11 |";
+        assert_eq!(parse_hexdump(hexdump(data)), Ok(data.to_vec()));
+
+        let dump = "00000000: 6361 6665 2020 7c0a  cafe  |.\n";
+        assert_eq!(parse_hexdump(dump), Ok(b"cafe  |\n".to_vec()));
+    }
+
+    #[test]
+    fn test_parse_hexdump_xxd() {
+        let dump = "00000000: 4865 6c6c 6f20 776f 726c 6421 0a0b 0c0d  Hello world!....\n\
+                    00000010: 0e0f 10                                  ...\n";
+        let mut expected = b"Hello world!".to_vec();
+        expected.extend(10..=16);
+        assert_eq!(parse_hexdump(dump), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_hexdump_repeated_lines() {
+        let dump =
+            "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+                    *\n\
+                    00000030  61 62                                             |ab|\n\
+                    00000032\n";
+        let mut expected = [0; 48].to_vec();
+        expected.extend_from_slice(b"ab");
+        assert_eq!(parse_hexdump(dump), Ok(expected));
+
+        let dump =
+            "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+                    *\n\
+                    00000040\n";
+        assert_eq!(parse_hexdump(dump), Ok([0; 64].to_vec()));
+    }
+
+    #[test]
+    fn test_parse_hexdump_errors() {
+        assert_eq!(
+            parse_hexdump("00000000  48 6g  |H.|"),
+            Err(ParseDumpError {
+                line: 1,
+                column: 15
+            })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  48 65\n00000002  6c 6\n"),
+            Err(ParseDumpError {
+                line: 2,
+                column: 14
+            })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  48 6 5"),
+            Err(ParseDumpError {
+                line: 1,
+                column: 14
+            })
+        );
+        assert_eq!(
+            parse_hexdump("\n  48 65"),
+            Err(ParseDumpError { line: 2, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  48 65\n00000004  6c 6c"),
+            Err(ParseDumpError { line: 2, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  00 00\n*\n00000005\n"),
+            Err(ParseDumpError { line: 3, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  00 00\n*\n"),
+            Err(ParseDumpError { line: 2, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump("ffffffffffffffffffff  00"),
+            Err(ParseDumpError { line: 1, column: 1 })
+        );
+    }
+
+    #[test]
+    fn test_parse_hexdump_huge_repeat() {
+        assert_eq!(
+            parse_hexdump("00000000  00\n*\nffffffffffffffff\n"),
+            Err(ParseDumpError { line: 3, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump("00000000  00 00\n*\nfffffffffffffff0\n"),
+            Err(ParseDumpError { line: 3, column: 1 })
+        );
+    }
+
+    #[test]
+    fn test_parse_hexdump_limited() {
+        let dump = "00000000  00 00 00 00\n*\n00000010  61 62\n";
+        let mut expected = [0; 16].to_vec();
+        expected.extend_from_slice(b"ab");
+        assert_eq!(parse_hexdump_limited(dump, 18), Ok(expected));
+        assert_eq!(
+            parse_hexdump_limited(dump, 17),
+            Err(ParseDumpError {
+                line: 3,
+                column: 14
+            })
+        );
+        assert_eq!(
+            parse_hexdump_limited(dump, 15),
+            Err(ParseDumpError { line: 3, column: 1 })
+        );
+        assert_eq!(
+            parse_hexdump_limited("00000000  00 01 02", 2),
+            Err(ParseDumpError {
+                line: 1,
+                column: 17
+            })
+        );
+    }
+}
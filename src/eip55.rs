@@ -0,0 +1,168 @@
+//! Mixed-case checksummed hex as used for Ethereum addresses (EIP-55).
+
+use crate::{decode_array_checked, encode, encode_with_case_mask, FromHexError};
+use alloc::string::String;
+use core::fmt;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The error type for verifying a checksummed address.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecksumError {
+    /// The address isn't a valid 20-byte hex string.
+    Hex(FromHexError),
+
+    /// The address is valid hex, but its capitalization doesn't match the
+    /// checksum.
+    InvalidChecksum,
+}
+
+impl From<FromHexError> for ChecksumError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumError {
+    fn description(&self) -> &str {
+        match *self {
+            Self::Hex(_) => "invalid address",
+            Self::InvalidChecksum => "invalid address checksum",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Hex(error) => Some(error),
+            Self::InvalidChecksum => None,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Hex(error) => write!(f, "Invalid address: {}", error),
+            Self::InvalidChecksum => write!(f, "Invalid address checksum"),
+        }
+    }
+}
+
+/// Encodes a 20-byte address as mixed-case checksummed hex string (EIP-55).
+///
+/// Each letter is uppercased if the corresponding nibble of the Keccak-256
+/// hash of the lowercase hex string is at least 8. No `0x` prefix is added.
+///
+/// # Example
+///
+/// ```
+/// let address = hex::decode_array_checked("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+/// assert_eq!(
+///     hex::encode_checksummed(&address),
+///     "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+/// );
+/// ```
+#[must_use]
+pub fn encode_checksummed(data: &[u8; 20]) -> String {
+    let lower = encode(data);
+
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(lower.as_bytes());
+    keccak.finalize(&mut hash);
+
+    let mut mask = [false; 40];
+    for (i, upper) in mask.iter_mut().enumerate() {
+        let nibble = hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+        *upper = nibble >= 8;
+    }
+    encode_with_case_mask(data, &mask)
+}
+
+/// Decodes a mixed-case checksummed address (EIP-55), verifying its
+/// capitalization.
+///
+/// An optional `0x` prefix is accepted. Addresses which are entirely lower or
+/// upper case carry no checksum and are rejected as well, unless they happen
+/// to match it.
+///
+/// # Example
+///
+/// ```
+/// assert!(hex::verify_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_ok());
+/// assert_eq!(
+///     hex::verify_checksummed("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+///     Err(hex::ChecksumError::InvalidChecksum)
+/// );
+/// ```
+pub fn verify_checksummed(s: &str) -> Result<[u8; 20], ChecksumError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    let address = decode_array_checked(digits)?;
+
+    if encode_checksummed(&address) != digits {
+        return Err(ChecksumError::InvalidChecksum);
+    }
+
+    Ok(address)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+
+    // Test vectors from https://eips.ethereum.org/EIPS/eip-55
+    const VECTORS: &[&str] = &[
+        "52908400098527886E0F7030069857D2E4169EE7",
+        "8617E340B3D01FA5F11F306F4090FD50E238070D",
+        "de709f2102306220921060314715629080e2fb77",
+        "27b1fdb04752bbc536007a920d24acb045561c26",
+        "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "fB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "dbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "D1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn test_encode_checksummed() {
+        for vector in VECTORS {
+            let address = decode_array_checked(vector).unwrap();
+            assert_eq!(encode_checksummed(&address), *vector);
+        }
+    }
+
+    #[test]
+    fn test_verify_checksummed() {
+        for vector in VECTORS {
+            let address = decode_array_checked(vector).unwrap();
+            assert_eq!(verify_checksummed(vector), Ok(address));
+            assert_eq!(verify_checksummed(&format!("0x{}", vector)), Ok(address));
+        }
+    }
+
+    #[test]
+    fn test_verify_checksummed_invalid() {
+        assert_eq!(
+            verify_checksummed("5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(ChecksumError::InvalidChecksum)
+        );
+        assert_eq!(
+            verify_checksummed("fb6916095ca1df60bb79ce92ce3ea74c37c5d359"),
+            Err(ChecksumError::InvalidChecksum)
+        );
+        assert_eq!(
+            verify_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+            Err(ChecksumError::Hex(FromHexError::UnexpectedLength {
+                expected: 40,
+                actual: 38
+            }))
+        );
+        assert_eq!(
+            verify_checksummed("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+            Err(ChecksumError::Hex(FromHexError::InvalidHexCharacter {
+                c: 'g',
+                index: 39
+            }))
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Reading and writing hex files.
+
+use crate::{decode, encode, val, FromHexError};
+use std::{error, fmt, fs, io, io::BufRead, path::Path};
+
+/// The error type for decoding a hex file.
+#[derive(Debug)]
+pub enum DecodeFileError {
+    /// The file couldn't be read.
+    Io(io::Error),
+
+    /// The file's contents aren't a valid hex string.
+    Hex(FromHexError),
+}
+
+impl From<io::Error> for DecodeFileError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<FromHexError> for DecodeFileError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
+}
+
+impl error::Error for DecodeFileError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Hex(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for DecodeFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read hex file: {}", error),
+            Self::Hex(error) => write!(f, "Invalid hex file: {}", error),
+        }
+    }
+}
+
+/// Reads the file at `path` and decodes its contents as hex string.
+///
+/// Trailing whitespace, such as a final newline, is ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// let key = hex::decode_file("key.hex").unwrap();
+/// ```
+pub fn decode_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, DecodeFileError> {
+    let contents = fs::read(path)?;
+    let end = contents
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(0, |last| last + 1);
+
+    Ok(decode(&contents[..end])?)
+}
+
+/// Encodes `data` as hex string using lowercase characters and writes it to
+/// the file at `path`, replacing any existing contents.
+///
+/// # Example
+///
+/// ```no_run
+/// hex::encode_file("key.hex", [0xca, 0xfe]).unwrap();
+/// ```
+pub fn encode_file<P: AsRef<Path>, T: AsRef<[u8]>>(path: P, data: T) -> io::Result<()> {
+    fs::write(path, encode(data))
+}
+
+/// The error type for decoding hex spread over several lines.
+#[derive(Debug)]
+pub enum DecodeLinesError {
+    /// Reading a line failed.
+    Io(io::Error),
+
+    /// An invalid character was found. `line` and `column` start at 1, and
+    /// the column counts bytes. Like `FromHexError::InvalidHexCharacter`, a
+    /// non-ASCII byte is reported as the `char` with the same value.
+    InvalidHexCharacter { c: char, line: usize, column: usize },
+
+    /// The total number of digits is odd.
+    OddLength,
+}
+
+impl From<io::Error> for DecodeLinesError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl error::Error for DecodeLinesError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DecodeLinesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read hex lines: {}", error),
+            Self::InvalidHexCharacter { c, line, column } => write!(
+                f,
+                "Invalid character '{}' at line {}, column {}",
+                c, line, column
+            ),
+            Self::OddLength => write!(f, "Odd number of digits"),
+        }
+    }
+}
+
+/// Reads hex wrapped over several lines, such as a PEM-like block, and
+/// decodes it.
+///
+/// Trailing whitespace is removed from each line, and blank lines are
+/// skipped. The remaining lines are decoded as if they were concatenated, so
+/// a byte's digits may even be split across lines. Leading whitespace isn't
+/// removed, and is reported like any other invalid character.
+///
+/// # Example
+///
+/// ```
+/// let block = "6b69\r\n\n7769\n";
+/// assert_eq!(hex::decode_lines(block.as_bytes()).unwrap(), b"kiwi");
+/// ```
+pub fn decode_lines<R: BufRead>(mut reader: R) -> Result<Vec<u8>, DecodeLinesError> {
+    let mut out = Vec::new();
+    let mut high = None;
+    let mut buffer = Vec::new();
+
+    for number in 1.. {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+
+        let end = buffer
+            .iter()
+            .rposition(|c| !c.is_ascii_whitespace())
+            .map_or(0, |last| last + 1);
+        for (column, &c) in buffer[..end].iter().enumerate() {
+            let value = val(c, column).map_err(|_| DecodeLinesError::InvalidHexCharacter {
+                c: c as char,
+                line: number,
+                column: column + 1,
+            })?;
+            match high.take() {
+                Some(high) => out.push(high << 4 | value),
+                None => high = Some(value),
+            }
+        }
+    }
+
+    if high.is_some() {
+        return Err(DecodeLinesError::OddLength);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.hex");
+
+        encode_file(&path, "foobar").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "666f6f626172");
+        assert_eq!(decode_file(&path).unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn test_decode_trailing_whitespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.hex");
+
+        fs::write(&path, "666f6f\r\n\n").unwrap();
+        assert_eq!(decode_file(&path).unwrap(), b"foo");
+
+        fs::write(&path, " \n").unwrap();
+        assert_eq!(decode_file(&path).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.hex");
+
+        match decode_file(&path) {
+            Err(DecodeFileError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::NotFound),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        fs::write(&path, " 66f\n").unwrap();
+        match decode_file(&path) {
+            Err(DecodeFileError::Hex(error)) => assert_eq!(
+                error,
+                FromHexError::InvalidHexCharacter { c: ' ', index: 0 }
+            ),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_decode_lines() {
+        let block = "666f6f62\n6172\n";
+        assert_eq!(decode_lines(block.as_bytes()).unwrap(), b"foobar");
+
+        let block = "66 \r\n\n  \n6f\t\n6f6\n2";
+        assert_eq!(decode_lines(block.as_bytes()).unwrap(), b"foob");
+
+        assert_eq!(decode_lines(&b""[..]).unwrap(), b"");
+        assert_eq!(decode_lines(&b"\n\n"[..]).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_decode_lines_errors() {
+        match decode_lines(&b"666f\n6g6f\n"[..]) {
+            Err(DecodeLinesError::InvalidHexCharacter { c, line, column }) => {
+                assert_eq!((c, line, column), ('g', 2, 2));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        match decode_lines(&b"666f\n\n 6f\n"[..]) {
+            Err(DecodeLinesError::InvalidHexCharacter { c, line, column }) => {
+                assert_eq!((c, line, column), (' ', 3, 1));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        match decode_lines(&b"666\nf6\n"[..]) {
+            Err(DecodeLinesError::OddLength) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_decode_lines_io_error() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+            }
+        }
+
+        match decode_lines(io::BufReader::new(FailingReader)) {
+            Err(DecodeLinesError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::BrokenPipe),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -12,13 +12,24 @@
 //! `encode_upper()` functions. If you need a bit more control, use the traits
 //! `ToHex` and `FromHex` instead.
 //!
+//! The functions which allocate are only available with the `alloc` feature
+//! (enabled by `std`, which is on by default). Without it, `encode_to_slice()`
+//! and `decode_to_slice()` can still be used on targets without an allocator.
+//!
+//! With the `simd` feature, encoding and decoding use NEON instructions on
+//! aarch64. Elsewhere, decoding processes eight characters at a time within a
+//! `u64`.
+//!
 //! # Example
 //!
 //! ```
 //! extern crate hex;
 //!
 //! fn main() {
+//! #   #[cfg(feature = "alloc")]
 //!     let hex_string = hex::encode("Hello world!");
+//! #   #[cfg(not(feature = "alloc"))]
+//! #   let hex_string = "48656c6c6f20776f726c6421";
 //!     println!("{}", hex_string); // Prints '48656c6c6f20776f726c6421'
 //! }
 //! ```
@@ -27,49 +38,195 @@
 #![allow(clippy::unreadable_literal)]
 #![warn(clippy::use_self)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
-#[cfg(not(feature = "std"))]
-use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::Cow, boxed::Box, collections::VecDeque, rc::Rc, string::String, sync::Arc, vec,
+    vec::Vec,
+};
 
 use core::fmt;
 use core::iter;
+use core::mem::MaybeUninit;
+use core::str::Utf8Error;
+
+#[cfg(feature = "alloc")]
+mod config;
+#[cfg(feature = "crc32")]
+mod crc;
+#[cfg(feature = "alloc")]
+mod dump;
+#[cfg(feature = "eip55")]
+mod eip55;
+#[cfg(feature = "std")]
+mod file;
+#[cfg(feature = "alloc")]
+mod prefix;
+mod simd;
+mod slice;
+mod table;
+#[cfg(feature = "alloc")]
+mod text;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "alloc")]
+pub use crate::config::HexConfig;
+#[cfg(feature = "crc32")]
+pub use crate::crc::{decode_with_crc32, encode_with_crc32, DecodeCrcError};
+#[cfg(feature = "alloc")]
+pub use crate::dump::{
+    hexdump, hexdump_with, parse_hexdump, parse_hexdump_limited, ParseDumpError,
+};
+#[cfg(feature = "eip55")]
+pub use crate::eip55::{encode_checksummed, verify_checksummed, ChecksumError};
+#[cfg(feature = "std")]
+pub use crate::file::{decode_file, decode_lines, encode_file, DecodeFileError, DecodeLinesError};
+#[cfg(feature = "alloc")]
+pub use crate::prefix::{decode_expect_prefix, DecodePrefixError};
+pub use crate::slice::HexSlice;
+pub use crate::table::{validate_table, InvalidTableError};
+#[cfg(feature = "alloc")]
+pub use crate::text::{decode_to_string, DecodeStringError};
+#[cfg(feature = "uuid")]
+pub use crate::uuid::decode_uuid;
+#[cfg(all(feature = "uuid", feature = "alloc"))]
+pub use crate::uuid::encode_uuid;
 
 /// Encoding values as hex string.
 ///
 /// This trait is implemented for all `T` which implement `AsRef<[u8]>`. This
-/// includes `String`, `str`, `Vec<u8>` and `[u8]`.
+/// includes `String`, `str`, `Vec<u8>`, `[u8]`, `&mut [u8]`, `Cow<[u8]>` and
+/// `[u8; N]` of any length, unlike `FromHex` which only supports a fixed set
+/// of array lengths.
 ///
 /// # Example
 ///
 /// ```
 /// use hex::ToHex;
+/// use std::borrow::Cow;
 ///
 /// println!("{}", "Hello world!".encode_hex::<String>());
+///
+/// let bytes: Cow<[u8]> = Cow::Owned(vec![0xca, 0xfe]);
+/// assert_eq!(bytes.encode_hex::<String>(), "cafe");
+///
+/// let mut buffer = [0xbe, 0xef];
+/// let bytes: &mut [u8] = &mut buffer;
+/// assert_eq!(bytes.encode_hex_upper::<String>(), "BEEF");
 /// ```
 ///
 /// *Note*: instead of using this trait, you might want to use `encode()`.
+/// Being generic over `FromIterator<char>`, the trait has to produce the
+/// string one `char` at a time, whereas `encode()` and `encode_upper()` write
+/// the digits as bytes straight into the string's buffer, which is
+/// considerably faster (see the `encode_string` benchmarks).
 pub trait ToHex {
     /// Encode the hex strict representing `self` into the result.. Lower case
     /// letters are used (e.g. `f9b4ca`)
+    #[must_use]
     fn encode_hex<T: iter::FromIterator<char>>(&self) -> T;
 
     /// Encode the hex strict representing `self` into the result.. Lower case
     /// letters are used (e.g. `F9B4CA`)
+    #[must_use]
     fn encode_hex_upper<T: iter::FromIterator<char>>(&self) -> T;
+
+    /// Write the hex string representing `self` into the given `fmt::Write`
+    /// sink without allocating. Lower case letters are used (e.g. `f9b4ca`)
+    fn encode_hex_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result;
+
+    /// Write the hex string representing `self` into the given `fmt::Write`
+    /// sink without allocating. Upper case letters are used (e.g. `F9B4CA`)
+    fn encode_hex_upper_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result;
+
+    /// Encode the hex string representing `self` into a `Box<str>`. Lower
+    /// case letters are used (e.g. `f9b4ca`)
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn encode_hex_boxed(&self) -> Box<str>;
+
+    /// Encode the hex string representing `self` into an `Arc<str>`, e.g. for
+    /// sharing or interning. Lower case letters are used (e.g. `f9b4ca`)
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn encode_hex_arc(&self) -> Arc<str>;
+
+    /// Encode the hex string representing `self` with a leading `0x`, e.g. for
+    /// printing addresses. Lower case letters are used (e.g. `0xf9b4ca`)
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn encode_hex_prefixed(&self) -> String;
+
+    /// Encode the hex string representing `self` with a leading `0x`, e.g. for
+    /// printing addresses. Upper case letters are used (e.g. `0xF9B4CA`)
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn encode_hex_upper_prefixed(&self) -> String;
+
+    /// Encode the hex string representing `self` with a space after every
+    /// `group` bytes, for easier reading. Lower case letters are used (e.g.
+    /// `f9b4 ca` for groups of 2). This is a shorthand for a `HexConfig`
+    /// with a `' '` separator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is `0`.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn encode_hex_spaced(&self, group: usize) -> String;
 }
 
 const HEX_CHARS_LOWER: &[u8; 16] = b"0123456789abcdef";
 const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
 
+const _: () = assert!(validate_table(HEX_CHARS_LOWER).is_ok());
+const _: () = assert!(validate_table(HEX_CHARS_UPPER).is_ok());
+
+/// Maps each byte to its two hex digits, so encoding needs a single lookup
+/// per byte.
+type HexPairs = [[u8; 2]; 256];
+
+static HEX_PAIRS_LOWER: HexPairs = hex_pairs(HEX_CHARS_LOWER);
+static HEX_PAIRS_UPPER: HexPairs = hex_pairs(HEX_CHARS_UPPER);
+
+const fn hex_pairs(chars: &[u8; 16]) -> HexPairs {
+    let mut pairs = [[0; 2]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        pairs[byte] = [chars[byte >> 4], chars[byte & 0x0f]];
+        byte += 1;
+    }
+    pairs
+}
+
+/// The case of the letters in encoded hex strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// Lowercase letters, e.g. `f9b4ca`.
+    Lower,
+    /// Uppercase letters, e.g. `F9B4CA`.
+    Upper,
+}
+
+impl Case {
+    fn table(self) -> &'static HexPairs {
+        match self {
+            Self::Lower => &HEX_PAIRS_LOWER,
+            Self::Upper => &HEX_PAIRS_UPPER,
+        }
+    }
+}
+
 struct BytesToHexChars<'a> {
     inner: ::core::slice::Iter<'a, u8>,
-    table: &'static [u8; 16],
+    table: &'static HexPairs,
     next: Option<char>,
 }
 
 impl<'a> BytesToHexChars<'a> {
-    fn new(inner: &'a [u8], table: &'static [u8; 16]) -> BytesToHexChars<'a> {
+    fn new(inner: &'a [u8], table: &'static HexPairs) -> Self {
         BytesToHexChars {
             inner: inner.iter(),
             table,
@@ -85,9 +242,9 @@ impl<'a> Iterator for BytesToHexChars<'a> {
         match self.next.take() {
             Some(current) => Some(current),
             None => self.inner.next().map(|byte| {
-                let current = self.table[(byte >> 4) as usize] as char;
-                self.next = Some(self.table[(byte & 0xf) as usize] as char);
-                current
+                let (high, low) = byte2hex(*byte, self.table);
+                self.next = Some(low as char);
+                high as char
             }),
         }
     }
@@ -108,25 +265,122 @@ impl<'a> iter::ExactSizeIterator for BytesToHexChars<'a> {
     }
 }
 
-fn encode_to_iter<T: iter::FromIterator<char>>(table: &'static [u8; 16], source: &[u8]) -> T {
+fn byte2hex(byte: u8, table: &HexPairs) -> (u8, u8) {
+    let [high, low] = table[byte as usize];
+
+    (high, low)
+}
+
+/// Encodes `source` into `output`, which has to be exactly twice as long.
+///
+/// This is the shared core of all encoders writing into a buffer.
+fn encode_slice_inner(table: &HexPairs, source: &[u8], output: &mut [u8]) {
+    debug_assert_eq!(source.len() * 2, output.len());
+    let done = simd::encode_prefix(table, source, output);
+    for (byte, pair) in source[done..]
+        .iter()
+        .zip(output[2 * done..].chunks_exact_mut(2))
+    {
+        pair.copy_from_slice(&table[*byte as usize]);
+    }
+}
+
+/// Encodes `source` into a new `String`.
+///
+/// The concrete encoders use this rather than collecting `BytesToHexChars`,
+/// which pushes each digit as a `char` and so checks its UTF-8 width and the
+/// remaining capacity every time.
+#[cfg(feature = "alloc")]
+fn encode_to_string(table: &HexPairs, source: &[u8]) -> String {
+    encode_to_string_with_capacity(table, source, 0)
+}
+
+/// Encodes `source` into a new `String` starting with `0x`.
+#[cfg(feature = "alloc")]
+fn encode_prefixed(table: &HexPairs, source: &[u8]) -> String {
+    let mut output = vec![0; source.len() * 2 + 2];
+    output[..2].copy_from_slice(b"0x");
+    encode_slice_inner(table, source, &mut output[2..]);
+    String::from_utf8(output).expect("hex digits are valid UTF-8")
+}
+
+/// Encodes `source` into a new `String` with room for `extra` more bytes.
+#[cfg(feature = "alloc")]
+fn encode_to_string_with_capacity(table: &HexPairs, source: &[u8], extra: usize) -> String {
+    let mut output = Vec::with_capacity(source.len() * 2 + extra);
+    output.resize(source.len() * 2, 0);
+    encode_slice_inner(table, source, &mut output);
+    String::from_utf8(output).expect("hex digits are valid UTF-8")
+}
+
+fn encode_to_iter<T: iter::FromIterator<char>>(table: &'static HexPairs, source: &[u8]) -> T {
     BytesToHexChars::new(source, table).collect()
 }
 
+fn write_to_fmt<W: fmt::Write>(
+    table: &'static HexPairs,
+    source: &[u8],
+    sink: &mut W,
+) -> fmt::Result {
+    BytesToHexChars::new(source, table).try_for_each(|c| sink.write_char(c))
+}
+
 impl<T: AsRef<[u8]>> ToHex for T {
     fn encode_hex<U: iter::FromIterator<char>>(&self) -> U {
-        encode_to_iter(HEX_CHARS_LOWER, self.as_ref())
+        encode_to_iter(&HEX_PAIRS_LOWER, self.as_ref())
     }
 
     fn encode_hex_upper<U: iter::FromIterator<char>>(&self) -> U {
-        encode_to_iter(HEX_CHARS_UPPER, self.as_ref())
+        encode_to_iter(&HEX_PAIRS_UPPER, self.as_ref())
+    }
+
+    fn encode_hex_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write_to_fmt(&HEX_PAIRS_LOWER, self.as_ref(), w)
+    }
+
+    fn encode_hex_upper_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write_to_fmt(&HEX_PAIRS_UPPER, self.as_ref(), w)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_boxed(&self) -> Box<str> {
+        encode_to_string(&HEX_PAIRS_LOWER, self.as_ref()).into_boxed_str()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_arc(&self) -> Arc<str> {
+        Arc::from(encode_to_string(&HEX_PAIRS_LOWER, self.as_ref()))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_prefixed(&self) -> String {
+        encode_prefixed(&HEX_PAIRS_LOWER, self.as_ref())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_upper_prefixed(&self) -> String {
+        encode_prefixed(&HEX_PAIRS_UPPER, self.as_ref())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn encode_hex_spaced(&self, group: usize) -> String {
+        HexConfig::new().separator(' ', group).encode(self)
     }
 }
 
 /// The error type for decoding a hex string into `Vec<u8>` or `[u8; N]`.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum FromHexError {
     /// An invalid character was found. Valid ones are: `0...9`, `a...f`
     /// or `A...F`.
+    ///
+    /// Functions decoding bytes report the offending byte as the `char` with
+    /// the same value, so a non-ASCII byte such as `0xC3` is reported as
+    /// `'Ã'`, and `index` is a byte offset. Use `invalid_byte()` to retrieve
+    /// the raw byte. `decode_str()` and `decode_u16()` report actual `char`s
+    /// and count `char`s or code units instead.
     InvalidHexCharacter { c: char, index: usize },
 
     /// A hex string's length needs to be even, as two digits correspond to
@@ -137,15 +391,117 @@ pub enum FromHexError {
     /// array, the hex string's length * 2 has to match the container's
     /// length.
     InvalidStringLength,
+
+    /// The hex string's length didn't match the length required by the
+    /// fixed sized output. Both lengths are given in hex digits.
+    UnexpectedLength { expected: usize, actual: usize },
+
+    /// The hex string was decoded into a `String`, but the decoded bytes
+    /// aren't valid UTF-8.
+    InvalidUtf8(Utf8Error),
+
+    /// The decoded output would exceed the given maximum number of bytes.
+    InputTooLong { max: usize, actual: usize },
+
+    /// The hex string was decoded into an integer, but its value doesn't fit
+    /// into the integer type.
+    Overflow,
+
+    /// A letter of the wrong case was found while decoding a hex string
+    /// which is required to be entirely lowercase or uppercase.
+    WrongCase {
+        c: char,
+        index: usize,
+        expected: Case,
+    },
+
+    /// A `\x` escape was cut off by the end of the input. `index` is the
+    /// position of its `\`.
+    TruncatedEscape { index: usize },
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for FromHexError {
     fn description(&self) -> &str {
+        self.as_str()
+    }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUtf8(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Converts the error into an `io::Error` of kind `InvalidData`, so `?` can
+/// be used in IO code. The original error is kept as the inner error.
+#[cfg(feature = "std")]
+impl From<FromHexError> for std::io::Error {
+    fn from(error: FromHexError) -> Self {
+        Self::new(std::io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl FromHexError {
+    /// Shifts the index of an invalid character by `offset`, for errors from
+    /// decoding a part of the input starting at `offset`.
+    fn with_offset(self, offset: usize) -> Self {
+        match self {
+            Self::InvalidHexCharacter { c, index } => Self::InvalidHexCharacter {
+                c,
+                index: index + offset,
+            },
+            Self::TruncatedEscape { index } => Self::TruncatedEscape {
+                index: index + offset,
+            },
+            error => error,
+        }
+    }
+
+    /// Returns a short static label for the kind of error, e.g.
+    /// `"invalid character"`.
+    ///
+    /// Unlike the `Display` implementation, this omits details such as the
+    /// position, so it doesn't need any formatting. This makes it cheap to
+    /// log without `std`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let error = hex::decode_to_slice("0g", &mut [0]).unwrap_err();
+    /// assert_eq!(error.as_str(), "invalid character");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
         match *self {
             Self::InvalidHexCharacter { .. } => "invalid character",
             Self::OddLength => "odd number of digits",
             Self::InvalidStringLength => "invalid string length",
+            Self::UnexpectedLength { .. } => "unexpected string length",
+            Self::InvalidUtf8(_) => "invalid UTF-8",
+            Self::InputTooLong { .. } => "input too long",
+            Self::Overflow => "integer overflow",
+            Self::WrongCase { .. } => "wrong case",
+            Self::TruncatedEscape { .. } => "truncated escape",
+        }
+    }
+
+    /// Returns the raw byte of an `InvalidHexCharacter` error from a function
+    /// decoding bytes.
+    ///
+    /// Returns `None` for other errors, and for characters which can't stem
+    /// from a single byte, i.e. those beyond `U+00FF`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let error = hex::decode_to_slice([0xc3, 0x30], &mut [0]).unwrap_err();
+    /// assert_eq!(error.invalid_byte(), Some(0xc3));
+    /// ```
+    pub fn invalid_byte(&self) -> Option<u8> {
+        match *self {
+            Self::InvalidHexCharacter { c, .. } if (c as u32) <= 0xff => Some(c as u8),
+            _ => None,
         }
     }
 }
@@ -158,19 +514,106 @@ impl fmt::Display for FromHexError {
             }
             Self::OddLength => write!(f, "Odd number of digits"),
             Self::InvalidStringLength => write!(f, "Invalid string length"),
+            Self::UnexpectedLength { expected, actual } => write!(
+                f,
+                "Invalid string length: expected {} digits, got {}",
+                expected, actual
+            ),
+            Self::InvalidUtf8(error) => write!(f, "Invalid UTF-8: {}", error),
+            Self::InputTooLong { max, actual } => write!(
+                f,
+                "Input too long: decodes to {} bytes, at most {} allowed",
+                actual, max
+            ),
+            Self::Overflow => write!(f, "Value too large for the integer type"),
+            Self::WrongCase { c, index, expected } => write!(
+                f,
+                "Invalid character '{}' at position {}: expected {}",
+                c,
+                index,
+                match expected {
+                    Case::Lower => "lowercase",
+                    Case::Upper => "uppercase",
+                }
+            ),
+            Self::TruncatedEscape { index } => {
+                write!(f, "Truncated escape at position {}", index)
+            }
         }
     }
 }
 
+/// The number of bytes `describe_error()` shows on either side of an invalid
+/// character.
+#[cfg(feature = "alloc")]
+const ERROR_CONTEXT: usize = 4;
+
+/// Describes a decoding error of `input`, showing the part of `input` around
+/// an invalid character.
+///
+/// This is intended for user facing messages, e.g. in command line tools. The
+/// error's index is taken to be a byte offset into `input`, as reported by all
+/// functions decoding bytes. Errors without an index are described like their
+/// `Display` implementation does.
+///
+/// # Example
+/// ```
+/// let input = "666f6f6g626172";
+/// let error = hex::decode(input).unwrap_err();
+/// assert_eq!(
+///     hex::describe_error(&error, input),
+///     "Invalid character 'g' at position 7 (…\"f6f6g6261\"…)"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn describe_error(err: &FromHexError, input: &str) -> String {
+    use core::fmt::Write;
+
+    let mut description = String::new();
+    write!(description, "{}", err).expect("writing to a String can't fail");
+
+    let index = match *err {
+        FromHexError::InvalidHexCharacter { index, .. }
+        | FromHexError::WrongCase { index, .. }
+        | FromHexError::TruncatedEscape { index } => index,
+        _ => return description,
+    };
+    if index >= input.len() {
+        return description;
+    }
+
+    let mut start = index.saturating_sub(ERROR_CONTEXT);
+    while !input.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (index + ERROR_CONTEXT + 1).min(input.len());
+    while !input.is_char_boundary(end) {
+        end += 1;
+    }
+
+    description.push_str(" (");
+    if start > 0 {
+        description.push('…');
+    }
+    write!(description, "{:?}", &input[start..end]).expect("writing to a String can't fail");
+    if end < input.len() {
+        description.push('…');
+    }
+    description.push(')');
+    description
+}
+
 /// Types that can be decoded from a hex string.
 ///
-/// This trait is implemented for `Vec<u8>` and small `u8`-arrays.
+/// This trait is implemented for `Vec<u8>`, `VecDeque<u8>` and `String` (with
+/// the `alloc` feature) and small `u8`-arrays.
 ///
 /// # Example
 ///
 /// ```
 /// use hex::FromHex;
 ///
+/// # #[cfg(feature = "alloc")]
 /// match Vec::from_hex("48656c6c6f20776f726c6421") {
 ///     Ok(vec) => {
 ///         for b in vec {
@@ -191,20 +634,138 @@ pub trait FromHex: Sized {
     /// Both, upper and lower case characters are valid and can even be
     /// mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
     fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error>;
+
+    /// Creates an instance of type `Self` from a hex string given as an
+    /// iterator of ASCII bytes, e.g. one produced by an adapter chain, or
+    /// fails with a custom error type.
+    ///
+    /// The default implementation collects the bytes and calls `from_hex()`.
+    /// Implementations can override it to decode without the intermediate
+    /// buffer, but have to report the same errors as `from_hex()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hex::FromHex;
+    ///
+    /// let digits = "6b-69-77-69".bytes().filter(|&c| c != b'-');
+    /// assert_eq!(<[u8; 4]>::from_hex_iter(digits), Ok(*b"kiwi"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn from_hex_iter<I: IntoIterator<Item = u8>>(iter: I) -> Result<Self, Self::Error> {
+        Self::from_hex(iter.into_iter().collect::<Vec<u8>>())
+    }
+}
+
+/// Maps each byte to its value as a hex digit, or `-1` if it isn't one.
+static HEX_DIGIT_VALUES: [i8; 256] = hex_digit_values(&[HEX_CHARS_LOWER, HEX_CHARS_UPPER]);
+
+/// Like `HEX_DIGIT_VALUES`, but only accepts lowercase letters.
+#[cfg(feature = "alloc")]
+static LOWER_HEX_DIGIT_VALUES: [i8; 256] = hex_digit_values(&[HEX_CHARS_LOWER]);
+
+const fn hex_digit_values(tables: &[&[u8; 16]]) -> [i8; 256] {
+    let mut values = [-1; 256];
+    let mut table = 0;
+    while table < tables.len() {
+        let mut i = 0;
+        while i < 16 {
+            values[tables[table][i] as usize] = i as i8;
+            i += 1;
+        }
+        table += 1;
+    }
+    values
 }
 
+#[inline]
 fn val(c: u8, idx: usize) -> Result<u8, FromHexError> {
-    match c {
-        b'A'..=b'F' => Ok(c - b'A' + 10),
-        b'a'..=b'f' => Ok(c - b'a' + 10),
-        b'0'..=b'9' => Ok(c - b'0'),
-        _ => Err(FromHexError::InvalidHexCharacter {
+    digit_value(&HEX_DIGIT_VALUES, c, idx)
+}
+
+/// Returns the value of `c`, which has to be a valid hex digit.
+#[cfg(all(feature = "alloc", feature = "simd"))]
+#[inline]
+fn valid_digit_value(c: u8) -> u8 {
+    debug_assert!(is_hex_digit(c));
+    HEX_DIGIT_VALUES[c as usize] as u8
+}
+
+#[inline]
+fn digit_value(values: &[i8; 256], c: u8, idx: usize) -> Result<u8, FromHexError> {
+    let value = values[c as usize];
+    if value < 0 {
+        return Err(FromHexError::InvalidHexCharacter {
             c: c as char,
             index: idx,
-        }),
+        });
+    }
+
+    Ok(value as u8)
+}
+
+#[cfg(feature = "alloc")]
+fn val_u16(unit: u16, idx: usize) -> Result<u8, FromHexError> {
+    if unit <= 0xff {
+        return val(unit as u8, idx);
+    }
+
+    Err(FromHexError::InvalidHexCharacter {
+        c: char::from_u32(u32::from(unit)).unwrap_or(char::REPLACEMENT_CHARACTER),
+        index: idx,
+    })
+}
+
+/// Converts a hex digit into its value without branching on `c`.
+///
+/// The second element is `0xff` if `c` is a valid hex digit and `0`
+/// otherwise. For invalid digits the value is `0`.
+#[cfg(feature = "alloc")]
+fn val_ct(c: u8) -> (u8, u8) {
+    let c = i16::from(c);
+    // Each range check is negative (and thus all ones after the arithmetic
+    // shift) only if `c` is within the range.
+    let digit = ((0x2f - c) & (c - 0x3a)) >> 8;
+    let lower = c | 0x20;
+    let alpha = ((0x60 - lower) & (lower - 0x67)) >> 8;
+    let value = (digit & (c - 0x30)) | (alpha & (lower - 0x57));
+
+    (value as u8, (digit | alpha) as u8)
+}
+
+/// Records the first invalid character of a string without branching on the
+/// character's validity.
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct FirstInvalid {
+    seen: u8,
+    c: u8,
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl FirstInvalid {
+    fn update(&mut self, c: u8, index: usize, valid: u8) {
+        let first = !valid & !self.seen;
+        let first_wide = usize::from(first & 1).wrapping_neg();
+        self.c = (self.c & !first) | (c & first);
+        self.index = (self.index & !first_wide) | (index & first_wide);
+        self.seen |= !valid;
+    }
+
+    fn into_result(self) -> Result<(), FromHexError> {
+        if self.seen == 0 {
+            Ok(())
+        } else {
+            Err(FromHexError::InvalidHexCharacter {
+                c: self.c as char,
+                index: self.index,
+            })
+        }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl FromHex for Vec<u8> {
     type Error = FromHexError;
 
@@ -214,10 +775,89 @@ impl FromHex for Vec<u8> {
             return Err(FromHexError::OddLength);
         }
 
-        hex.chunks(2)
-            .enumerate()
-            .map(|(i, pair)| Ok(val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?))
-            .collect()
+        // Allocating the exact size upfront and decoding into it avoids the
+        // repeated reallocations of collecting from an iterator without a
+        // useful size hint, which dominate for short inputs such as hashes.
+        let mut out = vec![0; hex.len() / 2];
+        decode_to_slice(hex, &mut out)?;
+        Ok(out)
+    }
+
+    fn from_hex_iter<I: IntoIterator<Item = u8>>(iter: I) -> Result<Self, Self::Error> {
+        let iter = iter.into_iter();
+        let mut out = Self::with_capacity(iter.size_hint().0 / 2);
+        let mut high = None;
+        let mut error = None;
+        let mut len = 0;
+
+        // Like `from_hex()`, an odd length takes precedence over an invalid
+        // character, so the input has to be consumed even after an error.
+        for (index, c) in iter.enumerate() {
+            len = index + 1;
+            if error.is_some() {
+                continue;
+            }
+
+            match val(c, index) {
+                Ok(value) => match high.take() {
+                    Some(high) => out.push(high << 4 | value),
+                    None => high = Some(value),
+                },
+                Err(e) => error = Some(e),
+            }
+        }
+
+        if len % 2 == 1 {
+            return Err(FromHexError::OddLength);
+        }
+        error.map_or(Ok(out), Err)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromHex for VecDeque<u8> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        Vec::from_hex(hex).map(Self::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromHex for Rc<[u8]> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        Vec::from_hex(hex).map(Self::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromHex for Arc<[u8]> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        Vec::from_hex(hex).map(Self::from)
+    }
+}
+
+/// Decodes the hex string and validates that the decoded bytes are UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use hex::FromHex;
+///
+/// assert_eq!(String::from_hex("6b697769").unwrap(), "kiwi");
+/// assert!(String::from_hex("ff").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+impl FromHex for String {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        Self::from_utf8(Vec::from_hex(hex)?)
+            .map_err(|error| FromHexError::InvalidUtf8(error.utf8_error()))
     }
 }
 
@@ -261,6 +901,34 @@ from_hex_array_impl! {
     4294967296
 }
 
+/// Decodes the hex string directly into a `GenericArray`, as used by crates
+/// built on `digest`. The hex string's length has to be twice the array's.
+///
+/// `GenericArray<u8, N>` implements `ToHex` as it implements `AsRef<[u8]>`.
+///
+/// # Example
+///
+/// ```
+/// use generic_array::{typenum::U4, GenericArray};
+/// use hex::FromHex;
+///
+/// let bytes = GenericArray::<u8, U4>::from_hex("6b697769").unwrap();
+/// assert_eq!(bytes.as_slice(), b"kiwi");
+/// ```
+// Recent 0.14 releases deprecate everything in favor of 1.x, but 0.14 is the
+// version `digest` based crates use.
+#[cfg(feature = "generic-array")]
+#[allow(deprecated)]
+impl<N: generic_array::ArrayLength<u8>> FromHex for generic_array::GenericArray<u8, N> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        let mut out = Self::default();
+        decode_to_slice(hex, &mut out)?;
+        Ok(out)
+    }
+}
+
 /// Encodes `data` as hex string using lowercase characters.
 ///
 /// Lowercase characters are used (e.g. `f9b4ca`). The resulting string's
@@ -274,8 +942,18 @@ from_hex_array_impl! {
 /// assert_eq!(hex::encode("Hello world!"), "48656c6c6f20776f726c6421");
 /// assert_eq!(hex::encode(vec![1, 2, 3, 15, 16]), "0102030f10");
 /// ```
+///
+/// Discarding the result is most likely a mistake, so the compiler warns
+/// about it:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// hex::encode("Hello world!");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
 pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
-    data.encode_hex()
+    encode_to_string(&HEX_PAIRS_LOWER, data.as_ref())
 }
 
 /// Encodes `data` as hex string using uppercase characters.
@@ -288,126 +966,4277 @@ pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
 /// assert_eq!(hex::encode_upper("Hello world!"), "48656C6C6F20776F726C6421");
 /// assert_eq!(hex::encode_upper(vec![1, 2, 3, 15, 16]), "0102030F10");
 /// ```
+#[cfg(feature = "alloc")]
+#[must_use]
 pub fn encode_upper<T: AsRef<[u8]>>(data: T) -> String {
-    data.encode_hex_upper()
+    encode_to_string(&HEX_PAIRS_UPPER, data.as_ref())
 }
 
-/// Decodes a hex string into raw bytes.
+/// Encodes a constant byte string as lowercase hex string at compile time,
+/// evaluating to a `&'static str`.
 ///
-/// Both, upper and lower case characters are valid in the input string and can
-/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+/// The input can be a byte string literal, an array literal, or any other
+/// constant expression of type `&[u8; N]` or `&[u8]`. As the result is a
+/// constant, this can be used to initialize `const`s and `static`s.
 ///
 /// # Example
 /// ```
-/// assert_eq!(
-///     hex::decode("48656c6c6f20776f726c6421"),
-///     Ok("Hello world!".to_owned().into_bytes())
-/// );
+/// const KIWI: &str = hex::encode!(b"kiwi");
+/// assert_eq!(KIWI, "6b697769");
 ///
-/// assert_eq!(hex::decode("123"), Err(hex::FromHexError::OddLength));
-/// assert!(hex::decode("foo").is_err());
+/// assert_eq!(hex::encode!([1, 2, 3, 15, 16]), "0102030f10");
 /// ```
-pub fn decode<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
-    FromHex::from_hex(data)
+#[macro_export]
+macro_rules! encode {
+    ([$($byte:expr),* $(,)?]) => {
+        $crate::encode!(&[$($byte),*])
+    };
+    ($data:expr) => {
+        $crate::__encode_const!($data, false)
+    };
 }
 
-/// Decode a hex string into a mutable bytes slice.
+/// Encodes a constant byte string as uppercase hex string at compile time,
+/// evaluating to a `&'static str`.
 ///
-/// Both, upper and lower case characters are valid in the input string and can
-/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+/// This works like `encode!`, but uses uppercase letters.
 ///
 /// # Example
 /// ```
-/// let mut bytes = [0u8; 4];
-/// assert_eq!(hex::decode_to_slice("6b697769", &mut bytes as &mut [u8]), Ok(()));
-/// assert_eq!(&bytes, b"kiwi");
+/// const KIWI: &str = hex::encode_upper!(b"\xca\xfe");
+/// assert_eq!(KIWI, "CAFE");
 /// ```
-pub fn decode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
-    let data = data.as_ref();
+#[macro_export]
+macro_rules! encode_upper {
+    ([$($byte:expr),* $(,)?]) => {
+        $crate::encode_upper!(&[$($byte),*])
+    };
+    ($data:expr) => {
+        $crate::__encode_const!($data, true)
+    };
+}
 
-    if data.len() % 2 != 0 {
-        return Err(FromHexError::OddLength);
-    }
-    if data.len() / 2 != out.len() {
-        return Err(FromHexError::InvalidStringLength);
-    }
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __encode_const {
+    ($data:expr, $upper:expr) => {{
+        const DATA: &[u8] = $data;
+        const HEX: &[u8; DATA.len() * 2] = &$crate::__encode_array(DATA, $upper);
+        const STR: &str = match ::core::str::from_utf8(HEX) {
+            Ok(hex) => hex,
+            Err(_) => panic!("hex digits are valid UTF-8"),
+        };
+        STR
+    }};
+}
 
-    for (i, byte) in out.iter_mut().enumerate() {
-        *byte = val(data[2 * i], 2 * i)? << 4 | val(data[2 * i + 1], 2 * i + 1)?;
+#[doc(hidden)]
+pub const fn __encode_array<const N: usize>(data: &[u8], upper: bool) -> [u8; N] {
+    let chars = if upper {
+        HEX_CHARS_UPPER
+    } else {
+        HEX_CHARS_LOWER
+    };
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < data.len() {
+        out[2 * i] = chars[(data[i] >> 4) as usize];
+        out[2 * i + 1] = chars[(data[i] & 0x0f) as usize];
+        i += 1;
     }
+    out
+}
 
-    Ok(())
+/// Encodes `data` as hex string using uppercase characters if `upper` is
+/// `true`, and lowercase ones otherwise.
+///
+/// This avoids duplicating code at call sites where the case is a runtime
+/// flag, e.g. a command line switch.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encode_cased([0xca, 0xfe], true), "CAFE");
+/// assert_eq!(hex::encode_cased([0xca, 0xfe], false), "cafe");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_cased<T: AsRef<[u8]>>(data: T, upper: bool) -> String {
+    let case = if upper { Case::Upper } else { Case::Lower };
+    encode_to_string(case.table(), data.as_ref())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Encodes `data` as hex string using lowercase characters, reserving room for
+/// `extra` more bytes.
+///
+/// The returned string has a capacity of at least `data.len() * 2 + extra`,
+/// so appending up to `extra` bytes to it doesn't reallocate. This is useful
+/// for building strings which contain the hex string and more.
+///
+/// # Example
+/// ```
+/// let mut s = hex::encode_with_capacity("kiwi", 4);
+/// assert!(s.capacity() >= 12);
+/// s.push_str(".bin");
+/// assert_eq!(s, "6b697769.bin");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_with_capacity<T: AsRef<[u8]>>(data: T, extra: usize) -> String {
+    encode_to_string_with_capacity(&HEX_PAIRS_LOWER, data.as_ref(), extra)
+}
 
-    #[test]
-    fn test_encode() {
-        assert_eq!(encode("foobar"), "666f6f626172");
+/// Encodes the bytes yielded by an iterator as hex string using lowercase
+/// characters.
+///
+/// This saves collecting generated byte sequences into a `Vec` first.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::encode_from_iter(0u8..4), "00010203");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_from_iter<I: IntoIterator<Item = u8>>(iter: I) -> String {
+    let iter = iter.into_iter();
+    let mut hex = String::with_capacity(iter.size_hint().0 * 2);
+    for byte in iter {
+        let [high, low] = HEX_PAIRS_LOWER[byte as usize];
+        hex.push(high as char);
+        hex.push(low as char);
     }
+    hex
+}
 
-    #[test]
+/// Encodes `data` as lowercase hex string, inserting `newline` after every
+/// `chars_per_line` hex characters.
+///
+/// No `newline` is appended after the last line. A `chars_per_line` of `0`
+/// disables wrapping. This is useful for hex dumps or for embedding long hex
+/// strings in emails or PEM-like blocks.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::encode_wrapped("kiwi", 4, "\n"), "6b69\n7769");
+/// assert_eq!(hex::encode_wrapped("kiwi", 0, "\n"), "6b697769");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_wrapped<T: AsRef<[u8]>>(data: T, chars_per_line: usize, newline: &str) -> String {
+    let hex = encode(data);
+    if chars_per_line == 0 || hex.len() <= chars_per_line {
+        return hex;
+    }
+
+    let lines = (hex.len() - 1) / chars_per_line + 1;
+    let mut wrapped = String::with_capacity(hex.len() + (lines - 1) * newline.len());
+    for (i, line) in hex.as_bytes().chunks(chars_per_line).enumerate() {
+        if i > 0 {
+            wrapped.push_str(newline);
+        }
+        wrapped.push_str(core::str::from_utf8(line).expect("hex digits are valid UTF-8"));
+    }
+    wrapped
+}
+
+/// Encodes `data` as hex string using lowercase characters, without
+/// allocating for empty input.
+///
+/// Apart from returning `Cow::Borrowed("")` if `data` is empty, this works
+/// exactly like `encode()`. This avoids needless allocations for callers which
+/// often encode empty byte slices, e.g. optional fields.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// assert_eq!(hex::encode_cow(""), Cow::Borrowed(""));
+/// assert_eq!(hex::encode_cow("kiwi"), "6b697769");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_cow<T: AsRef<[u8]>>(data: T) -> Cow<'static, str> {
+    let data = data.as_ref();
+    if data.is_empty() {
+        return Cow::Borrowed("");
+    }
+
+    Cow::Owned(encode(data))
+}
+
+/// Encodes `data` as hex string, choosing the case of each letter by a mask.
+///
+/// The `i`-th hex digit is uppercased if `upper_nibble_mask[i]` is `true`,
+/// and lowercase otherwise. Digits `0` to `9` are unaffected. This is the
+/// building block for checksums carried in the capitalization, such as EIP-55
+/// (see `encode_checksummed()` with the `eip55` feature).
+///
+/// # Panics
+///
+/// Panics if the length of `upper_nibble_mask` isn't `2 * data.len()`.
+///
+/// # Example
+///
+/// ```
+/// let mask = [true, false, false, true];
+/// assert_eq!(hex::encode_with_case_mask([0xca, 0xfe], &mask), "CafE");
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+#[must_use]
+pub fn encode_with_case_mask<T: AsRef<[u8]>>(data: T, upper_nibble_mask: &[bool]) -> String {
+    let data = data.as_ref();
+    assert_eq!(
+        upper_nibble_mask.len(),
+        2 * data.len(),
+        "case mask length must be twice the data length"
+    );
+
+    let mut hex = encode(data).into_bytes();
+    for (c, &upper) in hex.iter_mut().zip(upper_nibble_mask) {
+        if upper {
+            c.make_ascii_uppercase();
+        }
+    }
+    String::from_utf8(hex).expect("hex digits are valid UTF-8")
+}
+
+/// Encodes an optional value as hex string using lowercase characters,
+/// returning an empty string for `None`.
+///
+/// This saves matching on optional byte fields, e.g. in configs, at every
+/// call site. `decode_opt()` is the counterpart.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encode_opt(Some("kiwi")), "6b697769");
+/// assert_eq!(hex::encode_opt(None::<&[u8]>), "");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_opt<T: AsRef<[u8]>>(data: Option<T>) -> String {
+    data.map_or_else(String::new, encode)
+}
+
+/// Decodes an optional hex string into raw bytes, returning no bytes for
+/// `None`.
+///
+/// As with `decode()`, an empty string decodes to no bytes as well, so this
+/// reverses `encode_opt()` up to the distinction between `None` and empty
+/// values.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_opt(Some("6b697769")), Ok(b"kiwi".to_vec()));
+/// assert_eq!(hex::decode_opt(None::<&str>), Ok(vec![]));
+/// assert_eq!(hex::decode_opt(Some("")), Ok(vec![]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_opt<T: AsRef<[u8]>>(data: Option<T>) -> Result<Vec<u8>, FromHexError> {
+    data.map_or_else(|| Ok(Vec::new()), decode)
+}
+
+/// Encodes `value` as big-endian hex string using lowercase characters.
+///
+/// The result is zero-padded to 16 digits.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encode_u64(0xcafe), "000000000000cafe");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_u64(value: u64) -> String {
+    encode(value.to_be_bytes())
+}
+
+/// Encodes `value` as big-endian hex string using uppercase characters.
+///
+/// Apart from the characters' casing, this works exactly like `encode_u64()`.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_u64_upper(value: u64) -> String {
+    encode_upper(value.to_be_bytes())
+}
+
+/// Encodes `value` as big-endian hex string using lowercase characters.
+///
+/// The result is zero-padded to 32 digits.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encode_u128(0xcafe), "0000000000000000000000000000cafe");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_u128(value: u128) -> String {
+    encode(value.to_be_bytes())
+}
+
+/// Encodes `value` as big-endian hex string using uppercase characters.
+///
+/// Apart from the characters' casing, this works exactly like `encode_u128()`.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_u128_upper(value: u128) -> String {
+    encode_upper(value.to_be_bytes())
+}
+
+/// Decodes a big-endian hex string into an integer of at most `max_digits`
+/// significant digits.
+fn decode_be(hex: &str, max_digits: usize) -> Result<u128, FromHexError> {
+    if hex.is_empty() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let mut value = 0u128;
+    let mut digits = 0;
+    for (i, c) in hex.bytes().enumerate() {
+        let digit = val(c, i)?;
+        if value == 0 && digit == 0 {
+            continue;
+        }
+
+        digits += 1;
+        if digits > max_digits {
+            return Err(FromHexError::Overflow);
+        }
+        value = value << 4 | u128::from(digit);
+    }
+
+    Ok(value)
+}
+
+/// Decodes a big-endian hex string into a `u64`.
+///
+/// Any number of digits is accepted, including odd counts and leading zeros,
+/// as long as the value fits into a `u64`. Otherwise `FromHexError::Overflow`
+/// is returned. An empty string is rejected with
+/// `FromHexError::InvalidStringLength`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_u64("cafe"), Ok(0xcafe));
+/// assert_eq!(hex::decode_u64("ffffffffffffffff"), Ok(u64::MAX));
+/// assert_eq!(
+///     hex::decode_u64("10000000000000000"),
+///     Err(hex::FromHexError::Overflow)
+/// );
+/// ```
+pub fn decode_u64(hex: &str) -> Result<u64, FromHexError> {
+    decode_be(hex, 16).map(|value| value as u64)
+}
+
+/// Decodes a big-endian hex string into a `u128`.
+///
+/// This works like `decode_u64()`, but accepts up to 32 significant digits.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_u128("cafe"), Ok(0xcafe));
+/// ```
+pub fn decode_u128(hex: &str) -> Result<u128, FromHexError> {
+    decode_be(hex, 32)
+}
+
+/// Decodes a hex string into raw bytes.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     hex::decode("48656c6c6f20776f726c6421"),
+///     Ok("Hello world!".to_owned().into_bytes())
+/// );
+///
+/// assert_eq!(hex::decode("123"), Err(hex::FromHexError::OddLength));
+/// assert!(hex::decode("foo").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    FromHex::from_hex(data)
+}
+
+/// Decodes a hex string into raw bytes, reporting how many bytes could be
+/// decoded if it fails.
+///
+/// On failure, the error is returned along with the number of complete bytes
+/// preceding it. Streaming parsers can use this to keep the remainder of a
+/// chunk which was cut off mid-byte and retry once more data arrived. Unlike
+/// `decode()`, an invalid character is reported even if the length is odd,
+/// as long as it comes before the trailing digit.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_partial("6b6977"), Ok(b"kiw".to_vec()));
+/// assert_eq!(
+///     hex::decode_partial("6b69776"),
+///     Err((hex::FromHexError::OddLength, 3))
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_partial<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, (FromHexError, usize)> {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut pairs = data.chunks_exact(2);
+    for (i, pair) in pairs.by_ref().enumerate() {
+        let byte = val(pair[0], 2 * i)
+            .and_then(|high| Ok(high << 4 | val(pair[1], 2 * i + 1)?))
+            .map_err(|error| (error, out.len()))?;
+        out.push(byte);
+    }
+
+    if let [c] = *pairs.remainder() {
+        let error = val(c, data.len() - 1)
+            .err()
+            .unwrap_or(FromHexError::OddLength);
+        return Err((error, out.len()));
+    }
+
+    Ok(out)
+}
+
+/// Decodes the leading run of hex digits in `data`, returning the decoded
+/// bytes and the number of bytes of `data` consumed.
+///
+/// The run ends at the first byte which isn't a hex digit, or at the end of
+/// `data`. If it has an odd length, its last digit is left unconsumed. This
+/// helps extracting hex tokens from mixed input, such as `deadbeef port=80`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_first_run("6b697769 rest"), Ok((b"kiwi".to_vec(), 8)));
+/// assert_eq!(hex::decode_first_run("6b697=1"), Ok((b"ki".to_vec(), 4)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_first_run<T: AsRef<[u8]>>(data: T) -> Result<(Vec<u8>, usize), FromHexError> {
+    let data = data.as_ref();
+    let run = data
+        .iter()
+        .position(|&c| !is_hex_digit(c))
+        .unwrap_or(data.len());
+    let consumed = run - run % 2;
+
+    Ok((decode(&data[..consumed])?, consumed))
+}
+
+/// Decodes a hex string into raw bytes, dropping a trailing incomplete byte.
+///
+/// Returns the decoded bytes and whether a single trailing hex digit was
+/// dropped, which is useful for best-effort decoding of captured or streamed
+/// data. Invalid characters, including an invalid trailing one, are still
+/// rejected.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_lossy_tail("6b697769"), Ok((b"kiwi".to_vec(), false)));
+/// assert_eq!(hex::decode_lossy_tail("6b6977697"), Ok((b"kiwi".to_vec(), true)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_lossy_tail<T: AsRef<[u8]>>(data: T) -> Result<(Vec<u8>, bool), FromHexError> {
+    let data = data.as_ref();
+    let complete = data.len() - data.len() % 2;
+    let out = decode(&data[..complete])?;
+    if let Some(&c) = data.get(complete) {
+        val(c, complete)?;
+        return Ok((out, true));
+    }
+
+    Ok((out, false))
+}
+
+/// Decodes a hex string into raw bytes, requiring all letters to be
+/// lowercase.
+///
+/// This is useful for validating that input is in canonical form. An
+/// uppercase letter is rejected with `FromHexError::WrongCase`, pointing out
+/// the letter and the expected case.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_lower("cafe"), Ok(vec![0xca, 0xfe]));
+/// assert_eq!(
+///     hex::decode_lower("caFe"),
+///     Err(hex::FromHexError::WrongCase { c: 'F', index: 2, expected: hex::Case::Lower })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_lower<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    decode_cased(data.as_ref(), Case::Lower)
+}
+
+/// Decodes a hex string into raw bytes, requiring all letters to be
+/// uppercase.
+///
+/// Apart from the expected case, this works exactly like `decode_lower()`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_upper("CAFE"), Ok(vec![0xca, 0xfe]));
+/// assert!(hex::decode_upper("cafe").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_upper<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    decode_cased(data.as_ref(), Case::Upper)
+}
+
+/// Decodes a hex string into raw bytes, accepting only lowercase letters.
+///
+/// Unlike `decode_lower()`, which validates the case separately, this looks
+/// up each character in a table of lowercase digits only. This saves work
+/// when the input is known to be lowercase, e.g. because it was produced by
+/// `encode()`, but an uppercase letter is reported like any other invalid
+/// character, as `FromHexError::InvalidHexCharacter`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_lower_unchecked_case("cafe"), Ok(vec![0xca, 0xfe]));
+/// assert_eq!(
+///     hex::decode_lower_unchecked_case("caFe"),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: 'F', index: 2 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_lower_unchecked_case<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 == 1 {
+        return Err(FromHexError::OddLength);
+    }
+
+    data.chunks_exact(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let high = digit_value(&LOWER_HEX_DIGIT_VALUES, pair[0], 2 * i)?;
+            let low = digit_value(&LOWER_HEX_DIGIT_VALUES, pair[1], 2 * i + 1)?;
+            Ok(high << 4 | low)
+        })
+        .collect()
+}
+
+/// Encodes `data` as Base16 as specified by RFC 4648, i.e. as hex string
+/// using uppercase characters.
+///
+/// This is an alias of `encode_upper()` for code following the RFC's
+/// terminology.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::base16_encode("foobar"), "666F6F626172");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn base16_encode<T: AsRef<[u8]>>(data: T) -> String {
+    encode_upper(data)
+}
+
+/// Decodes Base16 as specified by RFC 4648 into raw bytes.
+///
+/// The RFC's alphabet is uppercase, so unless `lenient` is set, lowercase
+/// letters are rejected with `FromHexError::WrongCase` like `decode_upper()`
+/// does. With `lenient`, this works exactly like `decode()`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::base16_decode("CAFE", false), Ok(vec![0xca, 0xfe]));
+/// assert!(hex::base16_decode("cafe", false).is_err());
+/// assert_eq!(hex::base16_decode("cafe", true), Ok(vec![0xca, 0xfe]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn base16_decode<T: AsRef<[u8]>>(data: T, lenient: bool) -> Result<Vec<u8>, FromHexError> {
+    if lenient {
+        decode(data)
+    } else {
+        decode_upper(data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn decode_cased(data: &[u8], expected: Case) -> Result<Vec<u8>, FromHexError> {
+    if data.len() % 2 == 1 {
+        return Err(FromHexError::OddLength);
+    }
+
+    for (index, &c) in data.iter().enumerate() {
+        val(c, index)?;
+        let wrong_case = match expected {
+            Case::Lower => c.is_ascii_uppercase(),
+            Case::Upper => c.is_ascii_lowercase(),
+        };
+        if wrong_case {
+            return Err(FromHexError::WrongCase {
+                c: c as char,
+                index,
+                expected,
+            });
+        }
+    }
+
+    decode(data)
+}
+
+/// Decodes a hex string of little-endian words, e.g. from a dump of 32-bit
+/// values, reversing the order of the bytes within each word.
+///
+/// The input has to consist of whole words of `word_bytes` bytes each, i.e.
+/// its length has to be a multiple of `2 * word_bytes`. Otherwise
+/// `FromHexError::InvalidStringLength` is returned, or
+/// `FromHexError::OddLength` if the length is odd.
+///
+/// # Panics
+///
+/// Panics if `word_bytes` is `0`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_le_words("efbeadde", 4), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+/// assert_eq!(hex::decode_le_words("adde", 2), Ok(vec![0xde, 0xad]));
+/// assert!(hex::decode_le_words("efbead", 4).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn decode_le_words<T: AsRef<[u8]>>(
+    data: T,
+    word_bytes: usize,
+) -> Result<Vec<u8>, FromHexError> {
+    assert!(word_bytes > 0, "word size must be non-zero");
+
+    let mut decoded = decode(data)?;
+    if decoded.len() % word_bytes != 0 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    for word in decoded.chunks_exact_mut(word_bytes) {
+        word.reverse();
+    }
+    Ok(decoded)
+}
+
+/// Decodes a hex string into raw bytes, limiting the output's size.
+///
+/// If `data` would decode to more than `max_bytes` bytes,
+/// `FromHexError::InputTooLong` is returned before anything is allocated.
+/// This protects servers decoding untrusted input from huge allocations.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_limited("6b697769", 4), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_limited("6b697769", 3),
+///     Err(hex::FromHexError::InputTooLong { max: 3, actual: 4 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_limited<T: AsRef<[u8]>>(data: T, max_bytes: usize) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() / 2 > max_bytes {
+        return Err(FromHexError::InputTooLong {
+            max: max_bytes,
+            actual: data.len() / 2,
+        });
+    }
+
+    decode(data)
+}
+
+/// Decodes a hex string into raw bytes, panicking if it is invalid.
+///
+/// This is intended for input which is known to be valid, e.g. constants in
+/// tests. The panic message contains the `FromHexError` and is attributed to
+/// the caller's location.
+///
+/// # Panics
+///
+/// Panics if `data` isn't a valid hex string.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_unwrap("6b697769"), b"kiwi");
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+#[must_use]
+pub fn decode_unwrap<T: AsRef<[u8]>>(data: T) -> Vec<u8> {
+    match decode(data) {
+        Ok(bytes) => bytes,
+        Err(error) => panic!("failed to decode hex string: {}", error),
+    }
+}
+
+/// Decodes a hex string into raw bytes, reporting all errors instead of just
+/// the first one.
+///
+/// The whole input is scanned and every invalid character is reported in
+/// order, followed by `FromHexError::OddLength` if the input's length is odd.
+/// This allows giving complete feedback, e.g. in input validation UIs.
+///
+/// # Example
+/// ```
+/// use hex::FromHexError;
+///
+/// assert_eq!(hex::decode_collect_errors("6b697769"), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_collect_errors("6x69w"),
+///     Err(vec![
+///         FromHexError::InvalidHexCharacter { c: 'x', index: 1 },
+///         FromHexError::InvalidHexCharacter { c: 'w', index: 4 },
+///         FromHexError::OddLength,
+///     ])
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_collect_errors<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, Vec<FromHexError>> {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut errors = Vec::new();
+
+    let pairs = data.chunks_exact(2);
+    let remainder = pairs.remainder();
+    for (i, pair) in pairs.enumerate() {
+        match (val(pair[0], 2 * i), val(pair[1], 2 * i + 1)) {
+            (Ok(high), Ok(low)) => out.push(high << 4 | low),
+            (high, low) => errors.extend(high.err().into_iter().chain(low.err())),
+        }
+    }
+    if let [last] = *remainder {
+        errors.extend(val(last, data.len() - 1).err());
+        errors.push(FromHexError::OddLength);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(out)
+}
+
+/// Decodes a hex string into raw bytes, skipping any byte for which `skip`
+/// returns `true`.
+///
+/// The skipped bytes are dropped before pairing the remaining hex digits, so
+/// this can be used to decode input containing separators such as colons,
+/// dashes or whitespace. Indices in errors refer to positions in `data`,
+/// including skipped bytes.
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     hex::decode_skipping("6b:69-77 69", |c| b": -".contains(&c)),
+///     Ok(b"kiwi".to_vec())
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_skipping<T: AsRef<[u8]>, F: Fn(u8) -> bool>(
+    data: T,
+    skip: F,
+) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut high = None;
+    for (index, &c) in data.iter().enumerate() {
+        if skip(c) {
+            continue;
+        }
+
+        let value = val(c, index)?;
+        match high.take() {
+            Some(high) => out.push(high << 4 | value),
+            None => high = Some(value),
+        }
+    }
+
+    if high.is_some() {
+        return Err(FromHexError::OddLength);
+    }
+
+    Ok(out)
+}
+
+/// Decodes a hex string into raw bytes, ignoring a single trailing NUL byte.
+///
+/// This allows decoding buffers holding C strings without determining their
+/// length first. NUL bytes elsewhere, including a second trailing one, are
+/// rejected with `FromHexError::InvalidHexCharacter`. Use `decode_trim_nuls()`
+/// to ignore any number of trailing NUL bytes.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_trim_nul(b"6b697769\0"), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_trim_nul(b"6b\0a697769"),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: '\0', index: 2 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_trim_nul<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    decode(data.strip_suffix(b"\0").unwrap_or(data))
+}
+
+/// Decodes a hex string into raw bytes, ignoring all trailing NUL bytes.
+///
+/// This is useful for fixed size buffers which are padded with NUL bytes
+/// after the string. NUL bytes elsewhere are rejected with
+/// `FromHexError::InvalidHexCharacter`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_trim_nuls(b"6b697769\0\0\0"), Ok(b"kiwi".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_trim_nuls<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let len = data.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+    decode(&data[..len])
+}
+
+/// Decodes a hex string into raw bytes, ignoring the first `skip` bytes of
+/// `data`.
+///
+/// This allows decoding exports which put a marker, such as a UTF-8 byte
+/// order mark or a length byte, in front of the hex string. The skipped bytes
+/// can have any value. Indices in errors are relative to the start of `data`,
+/// including the skipped bytes. If `data` is shorter than `skip`,
+/// `FromHexError::InvalidStringLength` is returned.
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     hex::decode_skip_prefix_bytes(b"\xef\xbb\xbf6b697769", 3),
+///     Ok(b"kiwi".to_vec())
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_skip_prefix_bytes<T: AsRef<[u8]>>(
+    data: T,
+    skip: usize,
+) -> Result<Vec<u8>, FromHexError> {
+    let rest = data
+        .as_ref()
+        .get(skip..)
+        .ok_or(FromHexError::InvalidStringLength)?;
+    decode(rest).map_err(|error| error.with_offset(skip))
+}
+
+/// Decodes a string of `\x` escaped bytes into raw bytes.
+///
+/// Each byte has to be given as `\x` followed by two hex digits (e.g.
+/// `\x66\x6f\x6f`), as printed by Python or shells. Any other character is
+/// rejected with `FromHexError::InvalidHexCharacter`, with its index in
+/// `data`. An escape truncated by the end of the input is rejected with
+/// `FromHexError::TruncatedEscape`, with the index of its `\`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_escaped(r"\x6b\x69\x77\x69"), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_escaped(r"\x6b6977"),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: '6', index: 4 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_escaped<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() / 4);
+    for (i, escape) in data.chunks(4).enumerate() {
+        let index = 4 * i;
+        for (offset, (&c, &expected)) in escape.iter().zip(b"\\x").enumerate() {
+            if c != expected {
+                return Err(FromHexError::InvalidHexCharacter {
+                    c: c as char,
+                    index: index + offset,
+                });
+            }
+        }
+
+        match *escape {
+            [_, _, high, low] => out.push(val(high, index + 2)? << 4 | val(low, index + 3)?),
+            [_, _, high] => {
+                val(high, index + 2)?;
+                return Err(FromHexError::TruncatedEscape { index });
+            }
+            _ => return Err(FromHexError::TruncatedEscape { index }),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Decodes a hex string into raw bytes, accepting underscores between bytes.
+///
+/// This allows decoding values copied from source code, where `_` is used
+/// for grouping digits (e.g. `dead_beef`). An underscore splitting the two
+/// digits of a byte (e.g. `d_ead`) is rejected with
+/// `FromHexError::InvalidHexCharacter`. Indices in errors refer to positions
+/// in `data`, including underscores.
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     hex::decode_underscored("dead_beef"),
+///     Ok(vec![0xde, 0xad, 0xbe, 0xef])
+/// );
+/// assert_eq!(
+///     hex::decode_underscored("d_ead"),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: '_', index: 1 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_underscored<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut high = None;
+    for (index, &c) in data.iter().enumerate() {
+        if c == b'_' && high.is_none() {
+            continue;
+        }
+
+        let value = val(c, index)?;
+        match high.take() {
+            Some(high) => out.push(high << 4 | value),
+            None => high = Some(value),
+        }
+    }
+
+    if high.is_some() {
+        return Err(FromHexError::OddLength);
+    }
+
+    Ok(out)
+}
+
+/// Decodes a hex string split across multiple chunks into raw bytes.
+///
+/// The chunks are logically concatenated without copying them, so a pair of
+/// hex digits may straddle a chunk boundary. This is useful for input from
+/// scatter-gather IO. Indices in errors are offsets into the concatenated
+/// input. Since the total length isn't known upfront, an invalid character
+/// is reported even if the input's length is odd.
+///
+/// # Example
+/// ```
+/// let chunks: &[&[u8]] = &[b"6b6", b"97", b"769"];
+/// assert_eq!(hex::decode_chunks(chunks), Ok(b"kiwi".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_chunks<I>(chunks: I) -> Result<Vec<u8>, FromHexError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut out = Vec::new();
+    let mut high = None;
+    let mut index = 0;
+    for chunk in chunks {
+        for &c in chunk.as_ref() {
+            let value = val(c, index)?;
+            match high.take() {
+                Some(high) => out.push(high << 4 | value),
+                None => high = Some(value),
+            }
+            index += 1;
+        }
+    }
+
+    if high.is_some() {
+        return Err(FromHexError::OddLength);
+    }
+
+    Ok(out)
+}
+
+/// Decodes a hex string into raw bytes, reporting errors in terms of `char`s.
+///
+/// This works like `decode()`, but if `s` contains multibyte characters,
+/// `FromHexError::InvalidHexCharacter` contains the offending `char` and its
+/// index counted in `char`s rather than bytes, so it matches the position a
+/// user sees in their editor. Likewise, `FromHexError::OddLength` refers to
+/// the number of `char`s.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_str("6b697769"), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_str("éé0g"),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: 'é', index: 0 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_str(s: &str) -> Result<Vec<u8>, FromHexError> {
+    if !s.is_ascii() {
+        if s.chars().count() % 2 == 1 {
+            return Err(FromHexError::OddLength);
+        }
+        if let Some((index, c)) = s.chars().enumerate().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(FromHexError::InvalidHexCharacter { c, index });
+        }
+    }
+
+    decode(s)
+}
+
+/// Decodes a UTF-16 encoded hex string into raw bytes.
+///
+/// Each `u16` is treated as a single code unit, so wide strings as returned
+/// by Windows APIs can be decoded without converting them to a `String`
+/// first. Any code unit which isn't an ASCII hex digit is rejected with
+/// `FromHexError::InvalidHexCharacter`, whose index counts code units. Lone
+/// surrogates are reported as `char::REPLACEMENT_CHARACTER`.
+///
+/// # Example
+/// ```
+/// let wide: Vec<u16> = "6b697769".encode_utf16().collect();
+/// assert_eq!(hex::decode_u16(&wide), Ok(b"kiwi".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_u16<T: AsRef<[u16]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    data.chunks(2)
+        .enumerate()
+        .map(|(i, pair)| Ok(val_u16(pair[0], 2 * i)? << 4 | val_u16(pair[1], 2 * i + 1)?))
+        .collect()
+}
+
+/// Decodes a hex string into an `OsString` made of the raw bytes.
+///
+/// On Unix, OS strings such as file paths are arbitrary bytes, which needn't
+/// be valid UTF-8. This allows round-tripping them through hex.
+///
+/// # Example
+/// ```
+/// let path = hex::decode_os_string("2f746d702f6b697769").unwrap();
+/// assert_eq!(path, "/tmp/kiwi");
+/// ```
+#[cfg(all(unix, feature = "std"))]
+pub fn decode_os_string(hex: &str) -> Result<std::ffi::OsString, FromHexError> {
+    use std::os::unix::ffi::OsStringExt;
+
+    decode(hex).map(std::ffi::OsString::from_vec)
+}
+
+/// Decodes a hex string into raw bytes in constant time.
+///
+/// Unlike `decode()`, this doesn't branch on the values of the input's
+/// characters and always processes the whole input, so the time taken
+/// doesn't reveal where (or whether) an invalid character occurs. Only the
+/// input's length and the final outcome are observable. This makes it
+/// suitable for decoding secret material such as keys.
+///
+/// The tradeoffs are:
+///
+/// * it is slower than `decode()`, particularly for invalid input, which is
+///   not rejected early;
+/// * the error for invalid input still reports the first invalid character
+///   and its index, so callers handling secrets shouldn't log it;
+/// * the compiler isn't guaranteed to preserve the branchless arithmetic,
+///   so this is a best effort rather than a hard guarantee;
+/// * the partially decoded output of a failed call is dropped without being
+///   cleared.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_ct("6b697769"), Ok(b"kiwi".to_vec()));
+/// assert_eq!(hex::decode_ct("123"), Err(hex::FromHexError::OddLength));
+/// assert!(hex::decode_ct("6b69776g").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_ct<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut first_invalid = FirstInvalid::default();
+    for (i, pair) in data.chunks(2).enumerate() {
+        let (high, high_valid) = val_ct(pair[0]);
+        let (low, low_valid) = val_ct(pair[1]);
+        first_invalid.update(pair[0], 2 * i, high_valid);
+        first_invalid.update(pair[1], 2 * i + 1, low_valid);
+        out.push(high << 4 | low);
+    }
+
+    first_invalid.into_result().map(|()| out)
+}
+
+/// Decodes a hex string into raw bytes which are zeroized when dropped.
+///
+/// The bytes are decoded directly into the returned buffer, without any
+/// intermediate copies, so no decoded secret material is left behind in
+/// memory. If decoding fails, the partially decoded output is zeroized as
+/// well. Note that unlike `decode_ct()`, this doesn't run in constant time.
+///
+/// # Example
+/// ```
+/// let key = hex::decode_zeroizing("6b697769").unwrap();
+/// assert_eq!(*key, b"kiwi");
+/// ```
+#[cfg(feature = "zeroize")]
+pub fn decode_zeroizing<T: AsRef<[u8]>>(
+    data: T,
+) -> Result<zeroize::Zeroizing<Vec<u8>>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    let mut out = zeroize::Zeroizing::new(vec![0; data.len() / 2]);
+    decode_to_slice(data, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes a hex string into nibbles packed from the most significant one.
+///
+/// Unlike `decode()`, an odd number of digits is accepted: the final digit
+/// is stored in the high nibble of the last byte, with the low nibble set to
+/// `0`. The returned flag is `true` in this case, i.e. if the last byte holds
+/// only a half-byte.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_nibbles("abc"), Ok((vec![0xab, 0xc0], true)));
+/// assert_eq!(hex::decode_nibbles("abcd"), Ok((vec![0xab, 0xcd], false)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_nibbles<T: AsRef<[u8]>>(data: T) -> Result<(Vec<u8>, bool), FromHexError> {
+    let data = data.as_ref();
+    let pairs = data.chunks_exact(2);
+    let remainder = pairs.remainder();
+
+    let mut out = Vec::with_capacity(data.len() / 2 + 1);
+    for (i, pair) in pairs.enumerate() {
+        out.push(val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?);
+    }
+    if let [last] = *remainder {
+        out.push(val(last, data.len() - 1)? << 4);
+    }
+
+    Ok((out, !remainder.is_empty()))
+}
+
+/// Decodes a hex string into raw bytes, treating an odd-length string as if
+/// it had a leading `0`.
+///
+/// This is useful for sources which don't zero-pad the most significant byte,
+/// e.g. big integers (`f0f` is decoded as `0x0f0f`). Even-length input
+/// behaves exactly like `decode()`. Indices in errors refer to the input as
+/// given, without the implied `0`.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_odd_as_leading_zero("f0f"), Ok(vec![0x0f, 0x0f]));
+/// assert_eq!(hex::decode_odd_as_leading_zero("0f0f"), Ok(vec![0x0f, 0x0f]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_odd_as_leading_zero<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 == 0 {
+        return decode(data);
+    }
+
+    let mut out = Vec::with_capacity(data.len() / 2 + 1);
+    out.push(val(data[0], 0)?);
+    for (i, pair) in data[1..].chunks(2).enumerate() {
+        out.push(val(pair[0], 2 * i + 1)? << 4 | val(pair[1], 2 * i + 2)?);
+    }
+
+    Ok(out)
+}
+
+/// Lazily decodes a hex string, yielding one byte per pair of hex digits.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed. If an invalid character is found, the corresponding `Err` is
+/// the last item yielded. An odd-length input yields all complete pairs
+/// followed by `Err(FromHexError::OddLength)`. This allows processing the
+/// decoded bytes without allocating, stopping early if desired.
+///
+/// # Example
+/// ```
+/// let sum = hex::decode_iter(b"0102ff").try_fold(0u32, |sum, byte| {
+///     byte.map(|byte| sum + u32::from(byte))
+/// });
+/// assert_eq!(sum, Ok(258));
+///
+/// let mut bytes = hex::decode_iter(b"01g2");
+/// assert_eq!(bytes.next(), Some(Ok(1)));
+/// assert!(bytes.next().unwrap().is_err());
+/// assert_eq!(bytes.next(), None);
+/// ```
+pub fn decode_iter<'a>(data: &'a [u8]) -> impl Iterator<Item = Result<u8, FromHexError>> + 'a {
+    let pairs = data.chunks_exact(2);
+    let odd_length = if pairs.remainder().is_empty() {
+        None
+    } else {
+        Some(Err(FromHexError::OddLength))
+    };
+
+    pairs
+        .enumerate()
+        .map(|(i, pair)| Ok(val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?))
+        .chain(odd_length)
+        .scan(false, |failed, result| {
+            if *failed {
+                return None;
+            }
+            *failed = result.is_err();
+            Some(result)
+        })
+}
+
+/// An iterator adapter lazily decoding the hex digits yielded by another
+/// iterator.
+///
+/// This is the decoding counterpart of the iterator behind `ToHex`, and like
+/// `decode_iter()`, it works without `alloc`. Unlike `decode_iter()`, the
+/// input doesn't need to be a slice, so it can be any pull-based source, e.g.
+/// a filtered or chained iterator. Each item is a decoded byte, or the first
+/// error, after which iteration stops. As the length of the input isn't known
+/// upfront, an invalid character is reported before a final lone digit,
+/// which yields `Err(FromHexError::OddLength)`.
+///
+/// # Example
+/// ```
+/// use hex::DecodeIter;
+///
+/// let digits = b"6b 69 77 69".iter().copied().filter(|&c| c != b' ');
+/// let mut bytes = DecodeIter::new(digits);
+/// assert_eq!(bytes.next(), Some(Ok(b'k')));
+/// assert_eq!(bytes.map(Result::unwrap).last(), Some(b'i'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecodeIter<I> {
+    inner: I,
+    index: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = u8>> DecodeIter<I> {
+    /// Creates an adapter decoding the hex digits yielded by `inner`.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            index: 0,
+            done: false,
+        }
+    }
+
+    fn decode_next(&mut self, high: u8) -> Result<u8, FromHexError> {
+        let high = val(high, self.index)?;
+        match self.inner.next() {
+            Some(low) => Ok(high << 4 | val(low, self.index + 1)?),
+            None => Err(FromHexError::OddLength),
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for DecodeIter<I> {
+    type Item = Result<u8, FromHexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let high = match self.inner.next() {
+            Some(high) => high,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let result = self.decode_next(high);
+        self.done = result.is_err();
+        self.index += 2;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        // An error may end the iteration early, but each item consumes at
+        // least one digit.
+        let (_, upper) = self.inner.size_hint();
+        (0, upper.map(|upper| upper / 2 + upper % 2))
+    }
+}
+
+impl<I: Iterator<Item = u8>> iter::FusedIterator for DecodeIter<I> {}
+
+/// Decodes a hex string, appending the bytes to any `Extend<u8>` collection.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed. An odd-length input is rejected before anything is
+/// appended, but if an invalid character is found, the bytes decoded before it
+/// remain appended to `out`.
+///
+/// # Example
+/// ```
+/// let mut bytes = b"ki".to_vec();
+/// assert_eq!(hex::decode_extend("7769", &mut bytes), Ok(()));
+/// assert_eq!(bytes, b"kiwi");
+/// ```
+pub fn decode_extend<T: AsRef<[u8]>, E: Extend<u8>>(
+    data: T,
+    out: &mut E,
+) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    let mut error = None;
+    out.extend(decode_iter(data).scan((), |_, result| match result {
+        Ok(byte) => Some(byte),
+        Err(e) => {
+            error = Some(e);
+            None
+        }
+    }));
+
+    error.map_or(Ok(()), Err)
+}
+
+/// Decodes a hex string held in a `Vec<u8>`, reusing its allocation for the
+/// decoded bytes.
+///
+/// As the decoded bytes take up half the space of the hex string, they are
+/// written over its front, and the vector is truncated. Its capacity is
+/// unchanged, so no second allocation is needed.
+///
+/// Byte `i` is decoded from the digits at `2 * i` and `2 * i + 1`, and as
+/// `i <= 2 * i`, it never overwrites a digit which is yet to be read. This
+/// only uses safe indexing. If decoding fails, the vector, including any bytes
+/// already decoded into it, is dropped, so partially decoded data never
+/// escapes.
+///
+/// As the input and output overlap, the block decoders used by
+/// `decode_to_slice()` can't be used here. With the `simd` feature, the whole
+/// input is instead validated in blocks first, so the decoding loop doesn't
+/// need to check each digit. Only invalid input then goes through the checked
+/// loop, which reports the exact position of the first invalid character.
+///
+/// # Example
+/// ```
+/// let hex = b"6b697769".to_vec();
+/// assert_eq!(hex::decode_in_place(hex), Ok(b"kiwi".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_in_place(mut hex: Vec<u8>) -> Result<Vec<u8>, FromHexError> {
+    if hex.len() % 2 == 1 {
+        return Err(FromHexError::OddLength);
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        let validated = simd::validate_prefix(&hex);
+        if hex[validated..].iter().all(|&c| is_hex_digit(c)) {
+            // Writing index `i` only clobbers digits at or below `2 * i`,
+            // which have already been read.
+            for i in 0..hex.len() / 2 {
+                hex[i] = valid_digit_value(hex[2 * i]) << 4 | valid_digit_value(hex[2 * i + 1]);
+            }
+            hex.truncate(hex.len() / 2);
+            return Ok(hex);
+        }
+    }
+
+    // Writing index `i` only clobbers digits at or below `2 * i`, which have
+    // already been read.
+    for i in 0..hex.len() / 2 {
+        hex[i] = val(hex[2 * i], 2 * i)? << 4 | val(hex[2 * i + 1], 2 * i + 1)?;
+    }
+
+    hex.truncate(hex.len() / 2);
+    Ok(hex)
+}
+
+/// Decodes a hex string into `out`, placing the bytes starting at `offset`.
+///
+/// Bytes already in `out` at these positions are overwritten, and `out` is
+/// grown as needed. If `offset` is past the end of `out`, the gap is filled
+/// with zeros. This allows assembling a larger buffer, such as a framed
+/// message, piece by piece. If an invalid character is found, `out` may have
+/// been grown and partially overwritten.
+///
+/// # Example
+/// ```
+/// let mut frame = vec![0x01, 0x00, 0xff];
+/// hex::decode_into_vec_at("6b697769", &mut frame, 1).unwrap();
+/// assert_eq!(frame, b"\x01kiwi");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_into_vec_at<T: AsRef<[u8]>>(
+    data: T,
+    out: &mut Vec<u8>,
+    offset: usize,
+) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    let end = offset + data.len() / 2;
+    if end > out.len() {
+        out.resize(end, 0);
+    }
+    decode_to_slice(data, &mut out[offset..end])
+}
+
+/// A reusable buffer for decoding many hex strings without allocating for
+/// each of them.
+///
+/// The buffer only grows, so once it has decoded the longest input, decoding
+/// doesn't allocate anymore. The decoded bytes are borrowed from the buffer
+/// until the next call to `decode()`.
+///
+/// # Example
+/// ```
+/// let mut buf = hex::DecodeBuf::new();
+/// assert_eq!(buf.decode("6b697769"), Ok(&b"kiwi"[..]));
+/// assert_eq!(buf.decode("6b6977"), Ok(&b"kiw"[..]));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct DecodeBuf {
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl DecodeBuf {
+    /// Creates an empty buffer, which doesn't allocate until it's used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes a hex string into the buffer, returning the decoded bytes.
+    ///
+    /// This accepts the same input as `decode()` and fails in the same way.
+    pub fn decode<T: AsRef<[u8]>>(&mut self, data: T) -> Result<&[u8], FromHexError> {
+        let data = data.as_ref();
+        if data.len() % 2 != 0 {
+            return Err(FromHexError::OddLength);
+        }
+
+        self.buf.clear();
+        self.buf.resize(data.len() / 2, 0);
+        decode_to_slice(data, &mut self.buf)?;
+        Ok(&self.buf)
+    }
+}
+
+/// Lazily encodes `data` as hex string, yielding one `char` at a time in the
+/// given case.
+///
+/// The case can be chosen at runtime. Collecting the iterator is equivalent
+/// to `encode()` or `encode_upper()`, but it can be consumed without
+/// allocating.
+///
+/// # Example
+/// ```
+/// use hex::Case;
+///
+/// let chars: String = hex::encode_iter_cased(&[0xca, 0xfe], Case::Upper).collect();
+/// assert_eq!(chars, "CAFE");
+/// assert_eq!(hex::encode_iter_cased(b"kiwi", Case::Lower).len(), 8);
+/// ```
+pub fn encode_iter_cased(data: &[u8], case: Case) -> impl ExactSizeIterator<Item = char> + '_ {
+    BytesToHexChars::new(data, case.table())
+}
+
+/// Encodes `input` as hex string into a mutable bytes slice.
+///
+/// Lowercase characters are used (e.g. `f9b4ca`). The output slice's length
+/// has to be exactly twice the input's length, otherwise
+/// `FromHexError::InvalidStringLength` is returned.
+///
+/// # Example
+/// ```
+/// let mut bytes = [0u8; 8];
+/// assert_eq!(hex::encode_to_slice(b"kiwi", &mut bytes), Ok(()));
+/// assert_eq!(&bytes, b"6b697769");
+/// ```
+pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<(), FromHexError> {
+    let input = input.as_ref();
+
+    if input.len() * 2 != output.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    encode_slice_inner(&HEX_PAIRS_LOWER, input, output);
+    Ok(())
+}
+
+/// Encodes `input` as hex string into a mutable bytes slice using uppercase
+/// characters.
+///
+/// Apart from the characters' casing, this works exactly like
+/// `encode_to_slice()`.
+///
+/// # Example
+/// ```
+/// let mut bytes = [0u8; 4];
+/// assert_eq!(hex::encode_upper_to_slice([0xca, 0xfe], &mut bytes), Ok(()));
+/// assert_eq!(&bytes, b"CAFE");
+/// ```
+pub fn encode_upper_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<(), FromHexError> {
+    let input = input.as_ref();
+
+    if input.len() * 2 != output.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    encode_slice_inner(&HEX_PAIRS_UPPER, input, output);
+    Ok(())
+}
+
+/// Asserts at compile time that `M` is twice `N`.
+struct AssertEncodedLen<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> AssertEncodedLen<N, M> {
+    const OK: () = assert!(
+        N.checked_mul(2).is_some() && M == N * 2,
+        "the output array has to be twice as long as the input array"
+    );
+}
+
+/// Encodes a fixed sized array as lowercase hex string into a stack buffer,
+/// returning it as `&str`.
+///
+/// The buffer has to be exactly twice as long as the input, which is checked
+/// at compile time, so no allocation or runtime length check is needed.
+///
+/// # Example
+/// ```
+/// let mut buf = [0u8; 8];
+/// assert_eq!(hex::encode_to_str(b"kiwi", &mut buf), "6b697769");
+/// ```
+///
+/// A wrongly sized buffer doesn't compile:
+///
+/// ```compile_fail
+/// let mut buf = [0u8; 6];
+/// hex::encode_to_str(b"kiwi", &mut buf);
+/// ```
+pub fn encode_to_str<'a, const N: usize, const M: usize>(
+    data: &[u8; N],
+    buf: &'a mut [u8; M],
+) -> &'a str {
+    #[allow(clippy::let_unit_value)]
+    let () = AssertEncodedLen::<N, M>::OK;
+
+    encode_slice_inner(&HEX_PAIRS_LOWER, data, buf);
+    core::str::from_utf8(buf).expect("hex digits are valid UTF-8")
+}
+
+/// Wraps `data` to be displayed as lowercase hex string.
+///
+/// The hex string is written directly into the formatter, so this avoids
+/// allocating a `String` just for logging or formatting, e.g. in
+/// `log::info!("payload: {}", hex::display(&bytes))`. Use the returned
+/// `HexSlice`'s `UpperHex` implementation for uppercase characters.
+///
+/// # Example
+/// ```
+/// let bytes = b"kiwi".to_vec();
+/// assert_eq!(format!("payload: {}", hex::display(&bytes)), "payload: 6b697769");
+/// ```
+pub fn display<T: AsRef<[u8]> + ?Sized>(data: &T) -> HexSlice<'_> {
+    HexSlice::new(data.as_ref())
+}
+
+/// Wraps `data` to be displayed as lowercase hex string of at most
+/// `max_chars` characters, followed by the total length if it's truncated.
+///
+/// This keeps log lines readable for large payloads, e.g. a 4096 byte payload
+/// is displayed as `deadbeef…(4096 bytes)` with `max_chars` set to `8`.
+///
+/// # Example
+/// ```
+/// let bytes = [0xde, 0xad, 0xbe, 0xef, 0x00];
+/// assert_eq!(hex::display_truncated(&bytes, 8).to_string(), "deadbeef…(5 bytes)");
+/// assert_eq!(hex::display_truncated(&bytes, 10).to_string(), "deadbeef00");
+/// ```
+pub fn display_truncated<T: AsRef<[u8]> + ?Sized>(
+    data: &T,
+    max_chars: usize,
+) -> impl fmt::Display + '_ {
+    Truncated {
+        data: data.as_ref(),
+        max_chars,
+    }
+}
+
+struct Truncated<'a> {
+    data: &'a [u8],
+    max_chars: usize,
+}
+
+impl fmt::Display for Truncated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        BytesToHexChars::new(self.data, &HEX_PAIRS_LOWER)
+            .take(self.max_chars)
+            .try_for_each(|c| fmt::Write::write_char(f, c))?;
+        if self.data.len() * 2 > self.max_chars {
+            write!(f, "…({} bytes)", self.data.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `data` as hex string into the given `fmt::Write` sink.
+///
+/// Lowercase characters are used (e.g. `f9b4ca`). No intermediate buffer is
+/// allocated, so this can be used to format into a `String` or, on `no_std`
+/// targets, into any fixed capacity `fmt::Write` implementation.
+///
+/// # Example
+/// ```
+/// let mut s = String::from("key: ");
+/// hex::write_hex("kiwi", &mut s).unwrap();
+/// assert_eq!(s, "key: 6b697769");
+/// ```
+pub fn write_hex<W: fmt::Write, T: AsRef<[u8]>>(data: T, w: &mut W) -> fmt::Result {
+    write_to_fmt(&HEX_PAIRS_LOWER, data.as_ref(), w)
+}
+
+/// Writes `data` as hex string into the given `fmt::Write` sink using
+/// uppercase characters.
+///
+/// Apart from the characters' casing, this works exactly like `write_hex()`.
+///
+/// # Example
+/// ```
+/// let mut s = String::new();
+/// hex::write_hex_upper("kiwi", &mut s).unwrap();
+/// assert_eq!(s, "6B697769");
+/// ```
+pub fn write_hex_upper<W: fmt::Write, T: AsRef<[u8]>>(data: T, w: &mut W) -> fmt::Result {
+    write_to_fmt(&HEX_PAIRS_UPPER, data.as_ref(), w)
+}
+
+/// The size of the stack buffer `encode_to_writer()` encodes into.
+#[cfg(feature = "std")]
+const WRITER_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Writes `data` as hex string into the given `io::Write` sink.
+///
+/// Lowercase characters are used (e.g. `f9b4ca`). The data is encoded into a
+/// fixed size buffer on the stack, which is passed to the writer whenever it
+/// is full, so large inputs are written in a few large chunks without
+/// allocating.
+///
+/// # Example
+/// ```
+/// let mut out = Vec::new();
+/// hex::encode_to_writer("kiwi", &mut out).unwrap();
+/// assert_eq!(out, b"6b697769");
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: AsRef<[u8]>, W: std::io::Write>(
+    data: T,
+    out: &mut W,
+) -> std::io::Result<()> {
+    let mut buffer = [0; WRITER_BUFFER_SIZE];
+    for chunk in data.as_ref().chunks(WRITER_BUFFER_SIZE / 2) {
+        let hex = &mut buffer[..chunk.len() * 2];
+        encode_slice_inner(&HEX_PAIRS_LOWER, chunk, hex);
+        out.write_all(hex)?;
+    }
+
+    Ok(())
+}
+
+/// Returns whether `byte` is a hex digit, i.e. one of `0...9`, `a...f` or
+/// `A...F`. These are exactly the characters accepted by the decoding
+/// functions.
+///
+/// # Example
+/// ```
+/// assert!(hex::is_hex_digit(b'a'));
+/// assert!(hex::is_hex_digit(b'F'));
+/// assert!(!hex::is_hex_digit(b'g'));
+///
+/// const IS_DIGIT: bool = hex::is_hex_digit(b'7');
+/// assert!(IS_DIGIT);
+/// ```
+#[must_use]
+pub const fn is_hex_digit(byte: u8) -> bool {
+    byte.is_ascii_hexdigit()
+}
+
+/// Validates a hex string without decoding it.
+///
+/// This succeeds exactly if `decode()` would, and returns the same errors
+/// otherwise. Together with `byte_spans()`, this allows tooling such as
+/// editor plugins to map the bytes a string decodes to back to their
+/// positions in the string.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::validate_spans("6b697769"), Ok(()));
+/// assert_eq!(hex::validate_spans("6b69776"), Err(hex::FromHexError::OddLength));
+/// ```
+pub fn validate_spans<T: AsRef<[u8]>>(data: T) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+
+    data.iter()
+        .enumerate()
+        .try_for_each(|(i, &c)| val(c, i).map(|_| ()))
+}
+
+/// Returns the `(start, end)` byte ranges in a valid hex string of length
+/// `hex_len` which each decoded byte stems from.
+///
+/// The `i`-th decoded byte stems from the range `(2 * i, 2 * i + 2)`. A
+/// trailing odd digit doesn't form a range.
+///
+/// # Example
+/// ```
+/// let spans: Vec<_> = hex::byte_spans(6).collect();
+/// assert_eq!(spans, [(0, 2), (2, 4), (4, 6)]);
+/// ```
+pub fn byte_spans(hex_len: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..hex_len / 2).map(|i| (2 * i, 2 * i + 2))
+}
+
+/// Detects the case of the letters in a hex string.
+///
+/// Returns `Some(Case::Lower)` or `Some(Case::Upper)` if all letters share
+/// that case, and `None` if the case is mixed or there are no letters at all.
+/// This allows checking whether a string is in a canonical form without
+/// decoding it. The length of `data` isn't checked.
+///
+/// # Example
+/// ```
+/// use hex::Case;
+///
+/// assert_eq!(hex::detect_case("cafe00"), Ok(Some(Case::Lower)));
+/// assert_eq!(hex::detect_case("CAFE00"), Ok(Some(Case::Upper)));
+/// assert_eq!(hex::detect_case("CAfe00"), Ok(None));
+/// ```
+pub fn detect_case<T: AsRef<[u8]>>(data: T) -> Result<Option<Case>, FromHexError> {
+    let (mut lower, mut upper) = (false, false);
+    for (index, &c) in data.as_ref().iter().enumerate() {
+        val(c, index)?;
+        lower |= c.is_ascii_lowercase();
+        upper |= c.is_ascii_uppercase();
+    }
+
+    Ok(letter_case(lower, upper))
+}
+
+/// Decodes a hex string into raw bytes, also detecting the case of its
+/// letters.
+///
+/// The case is determined like `detect_case()` does, so it is `None` if the
+/// case is mixed or there are no letters at all. Unlike calling both
+/// functions, this only scans the input once, which suits pipelines both
+/// decoding and checking for a canonical form.
+///
+/// # Example
+/// ```
+/// use hex::Case;
+///
+/// assert_eq!(
+///     hex::decode_checking_case("cafe00"),
+///     Ok((vec![0xca, 0xfe, 0x00], Some(Case::Lower)))
+/// );
+/// assert_eq!(
+///     hex::decode_checking_case("CAfe00"),
+///     Ok((vec![0xca, 0xfe, 0x00], None))
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_checking_case<T: AsRef<[u8]>>(
+    data: T,
+) -> Result<(Vec<u8>, Option<Case>), FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 == 1 {
+        return Err(FromHexError::OddLength);
+    }
+
+    let (mut lower, mut upper) = (false, false);
+    let mut out = Vec::with_capacity(data.len() / 2);
+    for (i, pair) in data.chunks_exact(2).enumerate() {
+        out.push(val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?);
+        lower |= pair[0].is_ascii_lowercase() || pair[1].is_ascii_lowercase();
+        upper |= pair[0].is_ascii_uppercase() || pair[1].is_ascii_uppercase();
+    }
+
+    Ok((out, letter_case(lower, upper)))
+}
+
+/// Returns the case shared by all letters, given whether there are any of
+/// either case.
+fn letter_case(lower: bool, upper: bool) -> Option<Case> {
+    match (lower, upper) {
+        (true, false) => Some(Case::Lower),
+        (false, true) => Some(Case::Upper),
+        _ => None,
+    }
+}
+
+/// Compares two hex strings, ignoring ASCII case and a leading `0x` or `0X`.
+///
+/// The strings aren't decoded or validated, so this is cheap and also
+/// returns `false` early if their lengths differ. It is handy for comparing
+/// expected and computed hex strings, e.g. in tests.
+///
+/// # Example
+/// ```
+/// assert!(hex::eq_ignore_case("0xCAFE", "cafe"));
+/// assert!(!hex::eq_ignore_case("cafe", "cafe00"));
+/// ```
+#[must_use]
+pub fn eq_ignore_case(a: &str, b: &str) -> bool {
+    fn strip_prefix(s: &str) -> &str {
+        s.strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s)
+    }
+
+    let (a, b) = (strip_prefix(a), strip_prefix(b));
+    a.len() == b.len() && a.eq_ignore_ascii_case(b)
+}
+
+/// Decodes a hex string and compares it to `expected` in constant time, e.g.
+/// to verify a MAC or token.
+///
+/// The comparison doesn't stop at the first differing byte, but combines the
+/// differences of all bytes without branching on them, so its duration
+/// doesn't reveal how much of `expected` matches. It does reveal the length
+/// of `expected`, and decoding takes time depending on `data` only. Invalid
+/// input is rejected with an error like `decode()` does, even if its length
+/// differs from `expected`. Nothing is allocated.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_and_ct_eq("6b697769", b"kiwi"), Ok(true));
+/// assert_eq!(hex::decode_and_ct_eq("6b697760", b"kiwi"), Ok(false));
+/// assert!(hex::decode_and_ct_eq("6b69776", b"kiwi").is_err());
+/// ```
+pub fn decode_and_ct_eq<T: AsRef<[u8]>>(data: T, expected: &[u8]) -> Result<bool, FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 == 1 {
+        return Err(FromHexError::OddLength);
+    }
+
+    // Hiding the accumulated difference from the optimizer keeps it from
+    // exiting the loop early once a difference was found.
+    let mut diff = u8::from(data.len() / 2 != expected.len());
+    for (i, pair) in data.chunks_exact(2).enumerate() {
+        let byte = val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?;
+        diff = core::hint::black_box(diff | byte ^ expected.get(i).copied().unwrap_or(0));
+    }
+
+    Ok(diff == 0)
+}
+
+/// Decode a hex string into a mutable bytes slice.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+///
+/// The length of `out` has to be exactly half the length of `data`. In
+/// particular, an empty string decodes successfully into an empty slice, and
+/// fails with `FromHexError::InvalidStringLength` for any other slice.
+///
+/// # Example
+/// ```
+/// let mut bytes = [0u8; 4];
+/// assert_eq!(hex::decode_to_slice("6b697769", &mut bytes as &mut [u8]), Ok(()));
+/// assert_eq!(&bytes, b"kiwi");
+///
+/// assert_eq!(hex::decode_to_slice("", &mut []), Ok(()));
+/// ```
+pub fn decode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    if data.len() / 2 != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    if out.is_empty() {
+        return Ok(());
+    }
+
+    let done = simd::decode_prefix(data, out);
+    for (i, byte) in out.iter_mut().enumerate().skip(done) {
+        *byte = val(data[2 * i], 2 * i)? << 4 | val(data[2 * i + 1], 2 * i + 1)?;
+    }
+
+    Ok(())
+}
+
+/// Decode a hex string into a mutable bytes slice, reporting the expected and
+/// actual lengths if they differ.
+///
+/// This works like `decode_to_slice()`, but instead of
+/// `FromHexError::InvalidStringLength` a wrongly sized input yields
+/// `FromHexError::UnexpectedLength`, with both lengths in hex digits. So
+/// passing too much of a buffer, e.g. a field along with what follows it, is
+/// told apart from passing too little. The length is checked first, so this
+/// applies to odd lengths as well.
+///
+/// # Example
+/// ```
+/// let mut bytes = [0u8; 2];
+/// assert_eq!(hex::decode_to_slice_strict("6b69", &mut bytes), Ok(()));
+/// assert_eq!(
+///     hex::decode_to_slice_strict("6b6977", &mut bytes),
+///     Err(hex::FromHexError::UnexpectedLength { expected: 4, actual: 6 })
+/// );
+/// ```
+pub fn decode_to_slice_strict<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+    if data.len() != 2 * out.len() {
+        return Err(FromHexError::UnexpectedLength {
+            expected: 2 * out.len(),
+            actual: data.len(),
+        });
+    }
+
+    decode_to_slice(data, out)
+}
+
+/// Decode a hex string into the front of a mutable bytes slice, returning the
+/// decoded part.
+///
+/// Unlike `decode_to_slice()`, `out` may be larger than needed, so a single
+/// stack buffer can hold inputs of different lengths. If `out` is too small,
+/// `FromHexError::InvalidStringLength` is returned.
+///
+/// # Example
+/// ```
+/// let mut buffer = [0u8; 32];
+/// let bytes = hex::decode_to_slice_ref("6b697769", &mut buffer).unwrap();
+/// assert_eq!(bytes, b"kiwi");
+/// ```
+pub fn decode_to_slice_ref<T: AsRef<[u8]>>(
+    data: T,
+    out: &mut [u8],
+) -> Result<&mut [u8], FromHexError> {
+    let data = data.as_ref();
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    if data.len() / 2 > out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let out = &mut out[..data.len() / 2];
+    decode_to_slice(data, &mut *out)?;
+    Ok(out)
+}
+
+/// Decode a hex string into a slice of possibly uninitialized bytes, returning
+/// the initialized slice.
+///
+/// This works like `decode_to_slice()`, but saves zero-initializing the
+/// output buffer first. If an error is returned, `out` may be partially
+/// written to, but none of it should be considered initialized.
+///
+/// # Example
+/// ```
+/// use std::mem::MaybeUninit;
+///
+/// let mut buffer = [MaybeUninit::<u8>::uninit(); 4];
+/// let bytes = hex::decode_to_uninit_slice("6b697769", &mut buffer).unwrap();
+/// assert_eq!(bytes, b"kiwi");
+/// ```
+pub fn decode_to_uninit_slice<T: AsRef<[u8]>>(
+    data: T,
+    out: &mut [MaybeUninit<u8>],
+) -> Result<&mut [u8], FromHexError> {
+    let data = data.as_ref();
+
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    if data.len() / 2 != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    for (i, byte) in out.iter_mut().enumerate() {
+        byte.write(val(data[2 * i], 2 * i)? << 4 | val(data[2 * i + 1], 2 * i + 1)?);
+    }
+
+    // SAFETY: every element of `out` was written to above, and
+    // `MaybeUninit<u8>` has the same layout as `u8`.
+    Ok(unsafe { &mut *(out as *mut [MaybeUninit<u8>] as *mut [u8]) })
+}
+
+/// Decodes a hex string into a fixed sized array, reporting the expected and
+/// actual lengths if they differ.
+///
+/// This works like `<[u8; N] as FromHex>::from_hex()`, but instead of
+/// `FromHexError::InvalidStringLength` a wrongly sized input yields
+/// `FromHexError::UnexpectedLength`, which makes for more actionable error
+/// messages, e.g. when parsing keys given on the command line.
+///
+/// `data` has to be exactly `2 * N` bytes long. The expected length is
+/// computed with checked arithmetic, so even a pathological `N` can't make a
+/// wrongly sized input pass the check.
+///
+/// # Example
+/// ```
+/// assert_eq!(hex::decode_array_checked::<4, _>("6b697769"), Ok(*b"kiwi"));
+/// assert_eq!(
+///     hex::decode_array_checked::<4, _>("6b6977"),
+///     Err(hex::FromHexError::UnexpectedLength { expected: 8, actual: 6 })
+/// );
+/// ```
+pub fn decode_array_checked<const N: usize, T: AsRef<[u8]>>(
+    data: T,
+) -> Result<[u8; N], FromHexError> {
+    let data = data.as_ref();
+
+    match N.checked_mul(2) {
+        Some(expected) if expected == data.len() => {}
+        expected => {
+            return Err(FromHexError::UnexpectedLength {
+                expected: expected.unwrap_or(usize::MAX),
+                actual: data.len(),
+            })
+        }
+    }
+
+    let mut out = [0u8; N];
+    decode_to_slice(data, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes a hex string made up of two fixed sized fields, e.g. a header and
+/// a body, into a pair of arrays.
+///
+/// This saves slicing the input or the decoded bytes by hand. `data` has to
+/// be exactly `2 * (A + B)` digits long, otherwise
+/// `FromHexError::UnexpectedLength` is returned. Indices in errors are
+/// relative to the start of `data`, also for the second field.
+///
+/// # Example
+/// ```
+/// let (version, key) = hex::decode_fields::<1, 4, _>("016b697769").unwrap();
+/// assert_eq!(version, [0x01]);
+/// assert_eq!(&key, b"kiwi");
+/// ```
+pub fn decode_fields<const A: usize, const B: usize, T: AsRef<[u8]>>(
+    data: T,
+) -> Result<([u8; A], [u8; B]), FromHexError> {
+    let data = data.as_ref();
+    let expected = 2 * (A + B);
+    if data.len() != expected {
+        return Err(FromHexError::UnexpectedLength {
+            expected,
+            actual: data.len(),
+        });
+    }
+
+    let (first, second) = data.split_at(2 * A);
+    let mut fields = ([0u8; A], [0u8; B]);
+    decode_to_slice(first, &mut fields.0)?;
+    decode_to_slice(second, &mut fields.1).map_err(|error| error.with_offset(2 * A))?;
+    Ok(fields)
+}
+
+/// Decodes a hex string with separators into a fixed sized array, e.g. a
+/// colon separated MAC address or fingerprint.
+///
+/// Every occurrence of `separator` is skipped, wherever it is, and the
+/// remaining digits are decoded like `decode_array_checked()` does. So if
+/// there aren't exactly `2 * N` digits, `FromHexError::UnexpectedLength` is
+/// returned, counting digits only. Otherwise, indices in errors are relative
+/// to the start of `data`, including the separators.
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     hex::decode_array_with_separator("de:ad:be:ef", ':'),
+///     Ok([0xde, 0xad, 0xbe, 0xef])
+/// );
+/// assert_eq!(
+///     hex::decode_array_with_separator::<6, _>("de:ad:be:ef", ':'),
+///     Err(hex::FromHexError::UnexpectedLength { expected: 12, actual: 8 })
+/// );
+/// ```
+pub fn decode_array_with_separator<const N: usize, T: AsRef<[u8]>>(
+    data: T,
+    separator: char,
+) -> Result<[u8; N], FromHexError> {
+    let data = data.as_ref();
+    let mut separator_buffer = [0; 4];
+    let separator = separator.encode_utf8(&mut separator_buffer).as_bytes();
+
+    // The whole input is scanned even after an invalid character, as the
+    // length takes precedence like in `decode_array_checked()`.
+    let mut out = [0u8; N];
+    let mut digits = 0;
+    let mut error = None;
+    let mut index = 0;
+    while index < data.len() {
+        if data[index..].starts_with(separator) {
+            index += separator.len();
+            continue;
+        }
+
+        if digits < 2 * N && error.is_none() {
+            match val(data[index], index) {
+                Ok(value) => out[digits / 2] |= value << (4 * (1 - digits % 2)),
+                Err(e) => error = Some(e),
+            }
+        }
+        digits += 1;
+        index += 1;
+    }
+
+    if digits != 2 * N {
+        return Err(FromHexError::UnexpectedLength {
+            expected: 2 * N,
+            actual: digits,
+        });
+    }
+    error.map_or(Ok(out), Err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode() {
+        assert_eq!(encode("foobar"), "666f6f626172");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn test_decode() {
         assert_eq!(
-            decode("666f6f626172"),
-            Ok(String::from("foobar").into_bytes())
+            decode("666f6f626172"),
+            Ok(String::from("foobar").into_bytes())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_okay_str() {
+        assert_eq!(Vec::from_hex("666f6f626172").unwrap(), b"foobar");
+        assert_eq!(Vec::from_hex("666F6F626172").unwrap(), b"foobar");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_okay_bytes() {
+        assert_eq!(Vec::from_hex(b"666f6f626172").unwrap(), b"foobar");
+        assert_eq!(Vec::from_hex(b"666F6F626172").unwrap(), b"foobar");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_invalid_length() {
+        assert_eq!(Vec::from_hex("1").unwrap_err(), FromHexError::OddLength);
+        assert_eq!(
+            Vec::from_hex("666f6f6261721").unwrap_err(),
+            FromHexError::OddLength
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_invalid_char() {
+        assert_eq!(
+            Vec::from_hex("66ag").unwrap_err(),
+            FromHexError::InvalidHexCharacter { c: 'g', index: 3 }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_empty() {
+        assert_eq!(Vec::from_hex("").unwrap(), b"");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_whitespace() {
+        assert_eq!(
+            Vec::from_hex("666f 6f62617").unwrap_err(),
+            FromHexError::InvalidHexCharacter { c: ' ', index: 4 }
+        );
+    }
+
+    #[test]
+    pub fn test_from_hex_array() {
+        assert_eq!(
+            <[u8; 6] as FromHex>::from_hex("666f6f626172"),
+            Ok([0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72])
+        );
+
+        assert_eq!(
+            <[u8; 5] as FromHex>::from_hex("666f6f626172"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    pub fn test_encode_to_slice() {
+        let mut output = [0u8; 12];
+        assert_eq!(encode_to_slice(b"foobar", &mut output), Ok(()));
+        assert_eq!(&output, b"666f6f626172");
+
+        let mut output = [0u8; 11];
+        assert_eq!(
+            encode_to_slice(b"foobar", &mut output),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_write_hex_string() {
+        let mut output = String::new();
+        write_hex("foobar", &mut output).unwrap();
+        assert_eq!(output, "666f6f626172");
+
+        let mut output = String::new();
+        write_hex_upper([0xab, 0xcd], &mut output).unwrap();
+        assert_eq!(output, "ABCD");
+    }
+
+    #[test]
+    pub fn test_write_hex_counting() {
+        struct Counter(usize);
+
+        impl fmt::Write for Counter {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.len();
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        write_hex("foobar", &mut counter).unwrap();
+        assert_eq!(counter.0, 12);
+
+        let mut counter = Counter(0);
+        write_hex_upper([], &mut counter).unwrap();
+        assert_eq!(counter.0, 0);
+    }
+
+    #[test]
+    pub fn test_encode_upper_to_slice() {
+        let mut output = [0u8; 12];
+        assert_eq!(encode_upper_to_slice(b"foobar", &mut output), Ok(()));
+        assert_eq!(&output, b"666F6F626172");
+
+        let mut output = [0u8; 13];
+        assert_eq!(
+            encode_upper_to_slice(b"foobar", &mut output),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_encode_to_slice_matches_encode() {
+        use rand::{Rng, RngCore};
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut input = vec![0u8; rng.gen_range(0, 100)];
+            rng.fill_bytes(&mut input);
+            let mut output = vec![0u8; input.len() * 2];
+
+            encode_to_slice(&input, &mut output).unwrap();
+            assert_eq!(output, encode(&input).into_bytes());
+            assert_eq!(output, input.encode_hex::<String>().into_bytes());
+
+            encode_upper_to_slice(&input, &mut output).unwrap();
+            assert_eq!(output, encode_upper(&input).into_bytes());
+            assert_eq!(output, input.encode_hex_upper::<String>().into_bytes());
+        }
+    }
+
+    #[test]
+    pub fn test_decode_array_checked() {
+        assert_eq!(
+            decode_array_checked::<6, _>("666f6f626172"),
+            Ok([0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72])
+        );
+
+        assert_eq!(
+            decode_array_checked::<5, _>("666f6f626172"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 10,
+                actual: 12
+            })
+        );
+
+        assert_eq!(
+            decode_array_checked::<32, _>("666"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 64,
+                actual: 3
+            })
+        );
+
+        assert_eq!(
+            decode_array_checked::<2, _>("66ag"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_odd_as_leading_zero() {
+        assert_eq!(decode_odd_as_leading_zero("f0f").unwrap(), [0x0f, 0x0f]);
+        assert_eq!(decode_odd_as_leading_zero("1").unwrap(), [0x01]);
+        assert_eq!(decode_odd_as_leading_zero("0f0f").unwrap(), [0x0f, 0x0f]);
+        assert_eq!(decode_odd_as_leading_zero("").unwrap(), b"");
+
+        assert_eq!(
+            decode_odd_as_leading_zero("f0g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode_odd_as_leading_zero("x0f"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_val_ct() {
+        for c in 0..=255 {
+            match val(c, 0) {
+                Ok(value) => assert_eq!(val_ct(c), (value, 0xff)),
+                Err(_) => assert_eq!(val_ct(c), (0, 0)),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_ct() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_ct(encode(&bytes)), Ok(bytes.clone()));
+        assert_eq!(decode_ct(encode_upper(&bytes)), Ok(bytes));
+        assert_eq!(decode_ct("").unwrap(), b"");
+        assert_eq!(decode_ct("666f6f6261721"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_ct_reports_first_invalid() {
+        // The whole input is processed, so later invalid characters mustn't
+        // overwrite the first one.
+        assert_eq!(
+            decode_ct("66ag zz!"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(
+            decode_ct("x0ffffff"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 0 })
+        );
+        assert_eq!(
+            decode_ct(b"ffffff0\xff"),
+            Err(FromHexError::InvalidHexCharacter {
+                c: '\u{ff}',
+                index: 7
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_hex_pairs() {
+        for byte in 0..=255u8 {
+            let [high, low] = HEX_PAIRS_LOWER[byte as usize];
+            assert_eq!(high, HEX_CHARS_LOWER[(byte >> 4) as usize]);
+            assert_eq!(low, HEX_CHARS_LOWER[(byte & 0x0f) as usize]);
+
+            let [high, low] = HEX_PAIRS_UPPER[byte as usize];
+            assert_eq!(high, HEX_CHARS_UPPER[(byte >> 4) as usize]);
+            assert_eq!(low, HEX_CHARS_UPPER[(byte & 0x0f) as usize]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_matches_iter() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(encode(&bytes), bytes.encode_hex::<String>());
+        assert_eq!(encode_upper(&bytes), bytes.encode_hex_upper::<String>());
+
+        let mut output = [0u8; 512];
+        encode_to_slice(&bytes, &mut output).unwrap();
+        assert_eq!(&output[..], encode(&bytes).as_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_u16() {
+        let wide: Vec<u16> = "666F6f626172".encode_utf16().collect();
+        assert_eq!(decode_u16(&wide).unwrap(), b"foobar");
+        assert_eq!(decode_u16([0u16; 0]).unwrap(), b"");
+        assert_eq!(decode_u16([0x36u16]), Err(FromHexError::OddLength));
+
+        let wide: Vec<u16> = "66é6".encode_utf16().collect();
+        assert_eq!(
+            decode_u16(&wide),
+            Err(FromHexError::InvalidHexCharacter { c: 'é', index: 2 })
+        );
+
+        // U+0166 mustn't be truncated to the valid digit `f`.
+        assert_eq!(
+            decode_u16([0x36u16, 0x0166]),
+            Err(FromHexError::InvalidHexCharacter {
+                c: '\u{166}',
+                index: 1
+            })
+        );
+        assert_eq!(
+            decode_u16([0xd800u16, 0x36]),
+            Err(FromHexError::InvalidHexCharacter {
+                c: char::REPLACEMENT_CHARACTER,
+                index: 0
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_str() {
+        assert_eq!(decode_str("666f6f626172").unwrap(), b"foobar");
+        assert_eq!(decode_str("666f6f6261721"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_str("66ag"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+
+        assert_eq!(
+            decode_str("é0xg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'é', index: 0 })
+        );
+        assert_eq!(
+            decode_str("ab€x"),
+            Err(FromHexError::InvalidHexCharacter { c: '€', index: 2 })
+        );
+        assert_eq!(
+            decode_str("€€0x"),
+            Err(FromHexError::InvalidHexCharacter { c: '€', index: 0 })
+        );
+        assert_eq!(
+            decode_str("00x€"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+        assert_eq!(decode_str("é0x"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_cow() {
+        assert!(matches!(encode_cow(""), Cow::Borrowed("")));
+        assert!(matches!(encode_cow(Vec::new()), Cow::Borrowed("")));
+
+        let encoded = encode_cow("foobar");
+        assert!(matches!(encoded, Cow::Owned(_)));
+        assert_eq!(encoded, "666f6f626172");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_to_hex_cow() {
+        let owned: Cow<[u8]> = Cow::Owned(b"foobar".to_vec());
+        assert_eq!(owned.encode_hex::<String>(), "666f6f626172");
+
+        let borrowed: Cow<[u8]> = Cow::Borrowed(b"foobar");
+        assert_eq!(borrowed.encode_hex_upper::<String>(), "666F6F626172");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_to_hex_mut_slice() {
+        let mut buffer = *b"foobar";
+        let bytes: &mut [u8] = &mut buffer;
+        assert_eq!(bytes.encode_hex::<String>(), "666f6f626172");
+        assert_eq!(encode(bytes), "666f6f626172");
+    }
+
+    #[cfg(feature = "std")]
+    mod prop {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn test_roundtrip(data: Vec<u8>) {
+                prop_assert_eq!(decode(encode(&data)), Ok(data.clone()));
+                prop_assert_eq!(decode(encode_upper(&data)), Ok(data));
+            }
+
+            #[test]
+            fn test_encode_is_even_lowercase(data: Vec<u8>) {
+                let encoded = encode(&data);
+                prop_assert_eq!(encoded.len(), data.len() * 2);
+                prop_assert!(encoded.bytes().all(|c| HEX_CHARS_LOWER.contains(&c)));
+            }
+
+            #[test]
+            fn test_decode_arbitrary_str(data: String) {
+                check_decode_error(data.as_bytes());
+            }
+
+            #[test]
+            fn test_decode_arbitrary_bytes(data: Vec<u8>) {
+                check_decode_error(&data);
+            }
+
+            #[test]
+            fn test_decode_arbitrary_hex(data in "[0-9a-fA-F]*") {
+                check_decode_error(data.as_bytes());
+            }
+
+            #[test]
+            fn test_encode_matches_scalar(data: Vec<u8>) {
+                let expected: String = data.iter().map(|byte| format!("{:02x}", byte)).collect();
+                prop_assert_eq!(encode(&data), expected.clone());
+                prop_assert_eq!(encode_upper(&data), expected.to_uppercase());
+            }
+
+            #[test]
+            fn test_decode_in_place_matches_decode(data in "[0-9a-fA-F]{0,200}g?") {
+                let expected = decode(&data);
+                prop_assert_eq!(decode_in_place(data.into_bytes()), expected);
+            }
+
+            #[test]
+            fn test_decode_in_place_invalid_matches_decode(
+                data in "([0-9a-fA-F]{2}){0,100}",
+                invalid in any::<(proptest::sample::Index, u8)>(),
+            ) {
+                let mut data = data.into_bytes();
+                if !data.is_empty() {
+                    let (index, c) = invalid;
+                    let index = index.index(data.len());
+                    data[index] = c;
+                }
+
+                let expected = decode(&data);
+                prop_assert_eq!(decode_in_place(data), expected);
+            }
+
+            #[test]
+            fn test_decode_matches_scalar(
+                data in "([0-9a-fA-F]{2}){0,100}",
+                invalid in proptest::option::of((any::<proptest::sample::Index>(), "[^0-9a-fA-F]")),
+            ) {
+                let mut data = data.into_bytes();
+                if let (Some((index, c)), false) = (invalid, data.is_empty()) {
+                    let index = index.index(data.len());
+                    data[index] = c.as_bytes()[0];
+                }
+
+                let expected = data
+                    .chunks(2)
+                    .enumerate()
+                    .map(|(i, pair)| Ok(val(pair[0], 2 * i)? << 4 | val(pair[1], 2 * i + 1)?))
+                    .collect::<Result<Vec<_>, _>>();
+                prop_assert_eq!(decode(&data), expected);
+            }
+        }
+
+        fn check_decode_error(data: &[u8]) {
+            match decode(data) {
+                Ok(decoded) => assert_eq!(decoded.len() * 2, data.len()),
+                Err(FromHexError::OddLength) => assert_eq!(data.len() % 2, 1),
+                Err(FromHexError::InvalidHexCharacter { c, index }) => {
+                    assert_eq!(data[index] as char, c);
+                    assert!(!c.is_ascii_hexdigit());
+                }
+                Err(error) => panic!("unexpected error: {:?}", error),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_iter() {
+        let decoded: Result<Vec<u8>, _> = decode_iter(b"666f6f626172").collect();
+        assert_eq!(decoded.unwrap(), b"foobar");
+        assert_eq!(decode_iter(b"").next(), None);
+    }
+
+    #[test]
+    pub fn test_decode_iter_invalid_char() {
+        let mut iter = decode_iter(b"666g6f62");
+        assert_eq!(iter.next(), Some(Ok(0x66)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    pub fn test_decode_iter_odd_length() {
+        let mut iter = decode_iter(b"66f");
+        assert_eq!(iter.next(), Some(Ok(0x66)));
+        assert_eq!(iter.next(), Some(Err(FromHexError::OddLength)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = decode_iter(b"6xf");
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_extend_vec() {
+        let mut output = b"foo".to_vec();
+        assert_eq!(decode_extend("626172", &mut output), Ok(()));
+        assert_eq!(output, b"foobar");
+
+        assert_eq!(
+            decode_extend("6162x3", &mut output),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 4 })
+        );
+        assert_eq!(output, b"foobarab");
+
+        assert_eq!(
+            decode_extend("616", &mut output),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(output, b"foobarab");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_extend_vec_deque() {
+        let mut output = VecDeque::new();
+        output.push_front(b'f');
+        assert_eq!(decode_extend("6f6f", &mut output), Ok(()));
+        assert_eq!(output, b"foo");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_decode_unwrap() {
+        assert_eq!(decode_unwrap("666f6f626172"), b"foobar");
+
+        let error = std::panic::catch_unwind(|| decode_unwrap("66ag")).unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains(&FromHexError::InvalidHexCharacter { c: 'g', index: 3 }.to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_chunks() {
+        let chunks: &[&[u8]] = &[b"666", b"f6f", b"", b"626172"];
+        assert_eq!(decode_chunks(chunks).unwrap(), b"foobar");
+        assert_eq!(
+            decode_chunks(vec![String::from("66"), String::from("6f")]).unwrap(),
+            b"fo"
+        );
+        assert_eq!(decode_chunks(Vec::<&[u8]>::new()).unwrap(), b"");
+
+        let chunks: &[&[u8]] = &[b"666", b"f6"];
+        assert_eq!(decode_chunks(chunks), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_chunks_invalid_char() {
+        let chunks: &[&[u8]] = &[b"666f", b"6g62"];
+        assert_eq!(
+            decode_chunks(chunks),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+
+        let chunks: &[&[u8]] = &[b"666", b"x6f"];
+        assert_eq!(
+            decode_chunks(chunks),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_string() {
+        assert_eq!(String::from_hex("666f6f626172").unwrap(), "foobar");
+        assert_eq!(String::from_hex(encode("ünïcödé")).unwrap(), "ünïcödé");
+        assert_eq!(
+            String::from_hex("66ag"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+
+        match String::from_hex("66c3") {
+            Err(FromHexError::InvalidUtf8(error)) => assert_eq!(error.valid_up_to(), 1),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    pub fn test_hex_digit_values() {
+        // The table replaced this `match`, which serves as reference.
+        fn val_match(c: u8) -> Option<u8> {
+            match c {
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'0'..=b'9' => Some(c - b'0'),
+                _ => None,
+            }
+        }
+
+        for c in 0..=255 {
+            match val_match(c) {
+                Some(value) => assert_eq!(val(c, 7), Ok(value)),
+                None => assert_eq!(
+                    val(c, 7),
+                    Err(FromHexError::InvalidHexCharacter {
+                        c: c as char,
+                        index: 7
+                    })
+                ),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_invalid_byte() {
+        let error = decode_to_slice([0xc3, 0x30], &mut [0]).unwrap_err();
+        assert_eq!(
+            error,
+            FromHexError::InvalidHexCharacter {
+                c: '\u{c3}',
+                index: 0
+            }
+        );
+        assert_eq!(error.invalid_byte(), Some(0xc3));
+
+        let error = decode_to_slice(b"0g", &mut [0]).unwrap_err();
+        assert_eq!(error.invalid_byte(), Some(b'g'));
+
+        let error = FromHexError::InvalidHexCharacter { c: '€', index: 0 };
+        assert_eq!(error.invalid_byte(), None);
+        assert_eq!(FromHexError::OddLength.invalid_byte(), None);
+    }
+
+    #[test]
+    pub fn test_error_as_str() {
+        const LABEL: &str = FromHexError::OddLength.as_str();
+        assert_eq!(LABEL, "odd number of digits");
+
+        #[allow(invalid_from_utf8)]
+        let utf8_error = core::str::from_utf8(&[0xff]).unwrap_err();
+        let cases = [
+            (
+                FromHexError::InvalidHexCharacter { c: 'g', index: 1 },
+                "invalid character",
+            ),
+            (FromHexError::OddLength, "odd number of digits"),
+            (FromHexError::InvalidStringLength, "invalid string length"),
+            (
+                FromHexError::UnexpectedLength {
+                    expected: 4,
+                    actual: 2,
+                },
+                "unexpected string length",
+            ),
+            (FromHexError::InvalidUtf8(utf8_error), "invalid UTF-8"),
+            (
+                FromHexError::InputTooLong { max: 1, actual: 2 },
+                "input too long",
+            ),
+            (FromHexError::Overflow, "integer overflow"),
+            (
+                FromHexError::WrongCase {
+                    c: 'A',
+                    index: 0,
+                    expected: Case::Lower,
+                },
+                "wrong case",
+            ),
+            (
+                FromHexError::TruncatedEscape { index: 4 },
+                "truncated escape",
+            ),
+        ];
+        for (error, label) in &cases {
+            assert_eq!(error.as_str(), *label);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_skipping() {
+        let is_separator = |c| c == b':' || c == b'-';
+        assert_eq!(
+            decode_skipping("66:6f-6f:62-61:72", is_separator).unwrap(),
+            b"foobar"
+        );
+        assert_eq!(decode_skipping("::", is_separator).unwrap(), b"");
+
+        // A pair split by a separator is still a pair.
+        assert_eq!(decode_skipping("6:6", is_separator).unwrap(), b"f");
+        assert_eq!(
+            decode_skipping("66:6", is_separator),
+            Err(FromHexError::OddLength)
+        );
+
+        assert_eq!(
+            decode_skipping("66:6f 6f", is_separator),
+            Err(FromHexError::InvalidHexCharacter { c: ' ', index: 5 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_to_hex_large_array() {
+        let array = [0xab; 200];
+        let expected = "ab".repeat(200);
+        assert_eq!(array.encode_hex::<String>(), expected);
+        let array_ref: &[u8; 200] = &array;
+        assert_eq!(array_ref.encode_hex::<String>(), expected);
+        assert_eq!(encode(array), expected);
+        assert_eq!(encode(array_ref), expected);
+
+        let array = [0xcd; 1000];
+        assert_eq!(array.encode_hex_upper::<String>(), "CD".repeat(1000));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_collect_errors() {
+        assert_eq!(decode_collect_errors("666f6f626172").unwrap(), b"foobar");
+        assert_eq!(decode_collect_errors("").unwrap(), b"");
+
+        assert_eq!(
+            decode_collect_errors("66ag6f6z"),
+            Err(vec![
+                FromHexError::InvalidHexCharacter { c: 'g', index: 3 },
+                FromHexError::InvalidHexCharacter { c: 'z', index: 7 },
+            ])
+        );
+        assert_eq!(
+            decode_collect_errors("xy6"),
+            Err(vec![
+                FromHexError::InvalidHexCharacter { c: 'x', index: 0 },
+                FromHexError::InvalidHexCharacter { c: 'y', index: 1 },
+                FromHexError::OddLength,
+            ])
+        );
+        assert_eq!(
+            decode_collect_errors("66z"),
+            Err(vec![
+                FromHexError::InvalidHexCharacter { c: 'z', index: 2 },
+                FromHexError::OddLength,
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_iter_cased() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let lower: String = encode_iter_cased(&bytes, Case::Lower).collect();
+        assert_eq!(lower, encode(&bytes));
+        let upper: String = encode_iter_cased(&bytes, Case::Upper).collect();
+        assert_eq!(upper, encode_upper(&bytes));
+
+        let mut iter = encode_iter_cased(b"\xab", Case::Upper);
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some('A'));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some('B'));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_limited() {
+        assert_eq!(decode_limited("666f6f", 4).unwrap(), b"foo");
+        assert_eq!(decode_limited("666f6f62", 4).unwrap(), b"foob");
+        assert_eq!(decode_limited("", 0).unwrap(), b"");
+        assert_eq!(
+            decode_limited("666f6f6261", 4),
+            Err(FromHexError::InputTooLong { max: 4, actual: 5 })
+        );
+        assert_eq!(decode_limited("666f6f626", 4), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_limited("66", 0),
+            Err(FromHexError::InputTooLong { max: 0, actual: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_into_io_error() {
+        let error = FromHexError::InvalidHexCharacter { c: 'g', index: 3 };
+        let io_error = std::io::Error::from(error);
+        assert_eq!(io_error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(io_error.to_string(), error.to_string());
+        assert_eq!(
+            io_error
+                .into_inner()
+                .unwrap()
+                .downcast_ref::<FromHexError>(),
+            Some(&error)
+        );
+
+        fn read() -> std::io::Result<Vec<u8>> {
+            Ok(decode("66ag")?)
+        }
+        assert_eq!(read().unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_integers() {
+        assert_eq!(encode_u64(0), "0000000000000000");
+        assert_eq!(encode_u64(0x0123456789abcdef), "0123456789abcdef");
+        assert_eq!(encode_u64_upper(0x0123456789abcdef), "0123456789ABCDEF");
+        assert_eq!(encode_u128(u128::MAX), "f".repeat(32));
+        assert_eq!(
+            encode_u128_upper(0xab << 120),
+            String::from("AB") + &"0".repeat(30)
+        );
+    }
+
+    #[test]
+    pub fn test_decode_integers() {
+        assert_eq!(decode_u64("0123456789abcdef"), Ok(0x0123456789abcdef));
+        assert_eq!(decode_u64("ABCDEF"), Ok(0xabcdef));
+        assert_eq!(decode_u64("f"), Ok(0xf));
+        assert_eq!(decode_u64("0"), Ok(0));
+        assert_eq!(decode_u64("00000000000000000000ff"), Ok(0xff));
+        assert_eq!(decode_u64("ffffffffffffffff"), Ok(u64::MAX));
+        assert_eq!(decode_u128(&"f".repeat(32)), Ok(u128::MAX));
+
+        assert_eq!(decode_u64("10000000000000000"), Err(FromHexError::Overflow));
+        assert_eq!(decode_u128(&"1".repeat(33)), Err(FromHexError::Overflow));
+        assert_eq!(decode_u64(""), Err(FromHexError::InvalidStringLength));
+        assert_eq!(
+            decode_u64("0x12"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_underscored() {
+        assert_eq!(decode_underscored("666f_6f62_6172").unwrap(), b"foobar");
+        assert_eq!(decode_underscored("_66__6f_").unwrap(), b"fo");
+        assert_eq!(decode_underscored("666f6f").unwrap(), b"foo");
+        assert_eq!(decode_underscored("_").unwrap(), b"");
+
+        assert_eq!(
+            decode_underscored("d_ead"),
+            Err(FromHexError::InvalidHexCharacter { c: '_', index: 1 })
+        );
+        assert_eq!(
+            decode_underscored("de_a_d"),
+            Err(FromHexError::InvalidHexCharacter { c: '_', index: 4 })
+        );
+        assert_eq!(decode_underscored("de_a"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_nibbles() {
+        assert_eq!(decode_nibbles("666f6f"), Ok((b"foo".to_vec(), false)));
+        assert_eq!(decode_nibbles("666f6"), Ok((vec![0x66, 0x6f, 0x60], true)));
+        assert_eq!(decode_nibbles("f"), Ok((vec![0xf0], true)));
+        assert_eq!(decode_nibbles(""), Ok((vec![], false)));
+
+        assert_eq!(
+            decode_nibbles("66g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode_nibbles("6x6"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 })
+        );
+    }
+
+    #[test]
+    pub fn test_eq_ignore_case() {
+        assert!(eq_ignore_case("666f6f", "666f6f"));
+        assert!(eq_ignore_case("abcdef", "ABCdef"));
+        assert!(eq_ignore_case("0xabcdef", "ABCDEF"));
+        assert!(eq_ignore_case("0XABCDEF", "0xabcdef"));
+        assert!(eq_ignore_case("", "0x"));
+
+        assert!(!eq_ignore_case("abcdef", "abcdee"));
+        assert!(!eq_ignore_case("abcdef", "abcdef00"));
+        assert!(!eq_ignore_case("00abcdef", "0xabcdef"));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_vec_deque() {
+        let deque = VecDeque::from_hex("666f6f626172").unwrap();
+        assert_eq!(deque, Vec::from_hex("666f6f626172").unwrap());
+        assert_eq!(
+            VecDeque::from_hex("66ag"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(VecDeque::from_hex("666"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_cased() {
+        assert_eq!(encode_cased(b"foo\xab", false), "666f6fab");
+        assert_eq!(encode_cased(b"foo\xab", true), "666F6FAB");
+        assert_eq!(encode_cased("", true), "");
+    }
+
+    #[test]
+    pub fn test_validate_spans() {
+        assert_eq!(validate_spans("666f6F626172"), Ok(()));
+        assert_eq!(validate_spans(""), Ok(()));
+        assert_eq!(validate_spans("666"), Err(FromHexError::OddLength));
+        assert_eq!(
+            validate_spans("66ag"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    pub fn test_byte_spans() {
+        let mut spans = byte_spans(7);
+        assert_eq!(spans.next(), Some((0, 2)));
+        assert_eq!(spans.next(), Some((2, 4)));
+        assert_eq!(spans.next(), Some((4, 6)));
+        assert_eq!(spans.next(), None);
+
+        assert_eq!(byte_spans(0).next(), None);
+        assert_eq!(byte_spans(1).next(), None);
+        assert_eq!(byte_spans(2).last(), Some((0, 2)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_hex_boxed_and_arc() {
+        let boxed: Box<str> = "foobar".encode_hex_boxed();
+        assert_eq!(&*boxed, encode("foobar"));
+        assert_eq!(&*[0xffu8, 0x00].encode_hex_boxed(), "ff00");
+        assert_eq!(&*b"".encode_hex_boxed(), "");
+
+        let arc: Arc<str> = "foobar".encode_hex_arc();
+        assert_eq!(&*arc, encode("foobar"));
+        assert_eq!(&*vec![0xabu8, 0x01].encode_hex_arc(), "ab01");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_hex_spaced() {
+        let data = [0xde, 0xad, 0xbe, 0xef, 0x01];
+        assert_eq!(data.encode_hex_spaced(1), "de ad be ef 01");
+        assert_eq!(data.encode_hex_spaced(4), "deadbeef 01");
+        assert_eq!((&data[..4]).encode_hex_spaced(4), "deadbeef");
+        assert_eq!("foobar".encode_hex_spaced(2), "666f 6f62 6172");
+        assert_eq!(b"".encode_hex_spaced(1), "");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "separator group size must be non-zero")]
+    pub fn test_encode_hex_spaced_zero() {
+        let _ = "foobar".encode_hex_spaced(0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_hex_prefixed() {
+        assert_eq!([0xde, 0xad].encode_hex_prefixed(), "0xdead");
+        assert_eq!([0xde, 0xad].encode_hex_upper_prefixed(), "0xDEAD");
+        assert_eq!("foobar".encode_hex_prefixed(), "0x666f6f626172");
+        assert_eq!(b"".encode_hex_prefixed(), "0x");
+    }
+
+    #[test]
+    pub fn test_is_hex_digit() {
+        for &c in b"09afAF" {
+            assert!(is_hex_digit(c));
+        }
+        for &c in b"/:@G`g \0\xff" {
+            assert!(!is_hex_digit(c));
+        }
+        for c in 0..=255u8 {
+            assert_eq!(is_hex_digit(c), val(c, 0).is_ok());
+        }
+    }
+
+    #[test]
+    pub fn test_decode_to_uninit_slice() {
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 6];
+        let bytes = decode_to_uninit_slice("666f6F626172", &mut buffer).unwrap();
+        let mut expected = [0u8; 6];
+        decode_to_slice("666f6F626172", &mut expected).unwrap();
+        assert_eq!(bytes, &expected);
+        assert_eq!(bytes, b"foobar");
+
+        let mut empty: [MaybeUninit<u8>; 0] = [];
+        assert_eq!(decode_to_uninit_slice("", &mut empty), Ok(&mut [][..]));
+    }
+
+    #[test]
+    pub fn test_decode_to_uninit_slice_errors() {
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 2];
+        assert_eq!(
+            decode_to_uninit_slice("666", &mut buffer),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_to_uninit_slice("666f6f", &mut buffer),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_to_uninit_slice("66ag", &mut buffer),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_trim_nul() {
+        assert_eq!(decode_trim_nul(b"666f6f626172\0"), Ok(b"foobar".to_vec()));
+        assert_eq!(decode_trim_nul(b"666f6f626172"), Ok(b"foobar".to_vec()));
+        assert_eq!(decode_trim_nul(b"\0"), Ok(vec![]));
+        assert_eq!(
+            decode_trim_nul(b"666f6f6261\0\0\0"),
+            Err(FromHexError::InvalidHexCharacter { c: '\0', index: 10 })
+        );
+        assert_eq!(
+            decode_trim_nul(b"666f\0\0fa6172\0"),
+            Err(FromHexError::InvalidHexCharacter { c: '\0', index: 4 })
+        );
+        assert_eq!(decode_trim_nul(b"666\0"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_trim_nuls() {
+        assert_eq!(
+            decode_trim_nuls(b"666f6f626172\0\0\0"),
+            Ok(b"foobar".to_vec())
+        );
+        assert_eq!(decode_trim_nuls(b"666f6f626172"), Ok(b"foobar".to_vec()));
+        assert_eq!(decode_trim_nuls(b"\0\0"), Ok(vec![]));
+        assert_eq!(
+            decode_trim_nuls(b"66\0\0af\0"),
+            Err(FromHexError::InvalidHexCharacter { c: '\0', index: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_partial() {
+        assert_eq!(decode_partial("666f6f626172"), Ok(b"foobar".to_vec()));
+        assert_eq!(decode_partial(""), Ok(vec![]));
+        assert_eq!(decode_partial("666f6f6"), Err((FromHexError::OddLength, 3)));
+        assert_eq!(decode_partial("6"), Err((FromHexError::OddLength, 0)));
+        assert_eq!(
+            decode_partial("666f6g626172"),
+            Err((FromHexError::InvalidHexCharacter { c: 'g', index: 5 }, 2))
+        );
+        assert_eq!(
+            decode_partial("666fg6626"),
+            Err((FromHexError::InvalidHexCharacter { c: 'g', index: 4 }, 2))
+        );
+        assert_eq!(
+            decode_partial("666fg"),
+            Err((FromHexError::InvalidHexCharacter { c: 'g', index: 4 }, 2))
+        );
+    }
+
+    #[test]
+    pub fn test_encode_macro() {
+        const FOOBAR: &str = encode!(b"foobar");
+        assert_eq!(FOOBAR, "666f6f626172");
+        assert_eq!(encode!(b""), "");
+        assert_eq!(encode!([0x00, 0xff, 0x10]), "00ff10");
+
+        const BYTES: [u8; 2] = [0xca, 0xfe];
+        assert_eq!(encode!(&BYTES), "cafe");
+    }
+
+    #[test]
+    pub fn test_encode_upper_macro() {
+        const FOOBAR: &str = encode_upper!(b"foobar");
+        assert_eq!(FOOBAR, "666F6F626172");
+        assert_eq!(encode_upper!([0x00, 0xff, 0x1a]), "00FF1A");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_wrapped() {
+        let data: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            encode_wrapped(&data, 16, "\n"),
+            "0001020304050607\n08090a0b0c0d0e0f"
+        );
+        assert_eq!(encode_wrapped(&data[..8], 16, "\n"), "0001020304050607");
+        assert_eq!(encode_wrapped(&data, 0, "\n"), encode(&data));
+        assert_eq!(encode_wrapped(b"", 16, "\n"), "");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_wrapped_uneven() {
+        assert_eq!(encode_wrapped("foobar", 5, "\r\n"), "666f6\r\nf6261\r\n72");
+        assert_eq!(encode_wrapped("foobar", 11, "\n"), "666f6f62617\n2");
+        assert_eq!(encode_wrapped("foobar", 12, "\n"), "666f6f626172");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_lossy_tail() {
+        assert_eq!(
+            decode_lossy_tail("666f6f626172"),
+            Ok((b"foobar".to_vec(), false))
+        );
+        assert_eq!(
+            decode_lossy_tail("666f6f6261727"),
+            Ok((b"foobar".to_vec(), true))
+        );
+        assert_eq!(decode_lossy_tail(""), Ok((vec![], false)));
+        assert_eq!(decode_lossy_tail("a"), Ok((vec![], true)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_lossy_tail_invalid() {
+        assert_eq!(
+            decode_lossy_tail("666g6f6"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(
+            decode_lossy_tail("666f6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+        assert_eq!(decode_lossy_tail("666f6"), Ok((b"fo".to_vec(), true)));
+        assert_eq!(
+            decode_lossy_tail("666fg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_encode_to_writer() {
+        let mut out = Vec::new();
+        encode_to_writer("foobar", &mut out).unwrap();
+        assert_eq!(out, encode("foobar").into_bytes());
+
+        let mut out = Vec::new();
+        encode_to_writer(b"", &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_encode_to_writer_chunks() {
+        struct Chunks(Vec<Vec<u8>>);
+
+        impl std::io::Write for Chunks {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.push(buf.to_vec());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let data: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+        let mut out = Chunks(Vec::new());
+        encode_to_writer(&data, &mut out).unwrap();
+        let lengths: Vec<_> = out.0.iter().map(Vec::len).collect();
+        assert_eq!(lengths, [8192, 8192, 3616]);
+        assert_eq!(out.0.concat(), encode(&data).into_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_escaped() {
+        assert_eq!(
+            decode_escaped(r"\x66\x6f\x6F\x62\x61\x72"),
+            Ok(b"foobar".to_vec())
+        );
+        assert_eq!(decode_escaped(""), Ok(vec![]));
+        assert_eq!(
+            decode_escaped(r"\x66\x6"),
+            Err(FromHexError::TruncatedEscape { index: 4 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66\x"),
+            Err(FromHexError::TruncatedEscape { index: 4 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66\"),
+            Err(FromHexError::TruncatedEscape { index: 4 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x6b\x69\x77\x6"),
+            Err(FromHexError::TruncatedEscape { index: 12 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_escaped_invalid() {
+        assert_eq!(
+            decode_escaped("666f"),
+            Err(FromHexError::InvalidHexCharacter { c: '6', index: 0 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66\X6f"),
+            Err(FromHexError::InvalidHexCharacter { c: 'X', index: 5 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66\x6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 7 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66\xg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 6 })
+        );
+        assert_eq!(
+            decode_escaped(r"\x66 \x6f"),
+            Err(FromHexError::InvalidHexCharacter { c: ' ', index: 4 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_buf() {
+        let mut buf = DecodeBuf::new();
+        assert_eq!(buf.decode("666f6f626172"), Ok(&b"foobar"[..]));
+        let capacity = buf.buf.capacity();
+        assert!(capacity >= 6);
+
+        assert_eq!(buf.decode("6b697769"), Ok(&b"kiwi"[..]));
+        assert_eq!(buf.decode(""), Ok(&b""[..]));
+        assert_eq!(buf.decode("666f6F626172"), Ok(&b"foobar"[..]));
+        assert_eq!(buf.buf.capacity(), capacity);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_buf_errors() {
+        let mut buf = DecodeBuf::new();
+        assert_eq!(buf.decode("666"), Err(FromHexError::OddLength));
+        assert_eq!(
+            buf.decode("666g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(buf.decode("6b697769"), Ok(&b"kiwi"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    pub fn test_decode_zeroizing() {
+        let decoded: zeroize::Zeroizing<Vec<u8>> = decode_zeroizing("666f6f626172").unwrap();
+        assert_eq!(*decoded, b"foobar");
+        assert_eq!(decode_zeroizing("666"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_zeroizing("666g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_to_slice_ref() {
+        let mut exact = [0u8; 6];
+        let decoded = decode_to_slice_ref("666f6f626172", &mut exact).unwrap();
+        assert_eq!(decoded, b"foobar");
+
+        let mut surplus = [0xffu8; 8];
+        let decoded = decode_to_slice_ref("666f6f", &mut surplus).unwrap();
+        assert_eq!(decoded, b"foo");
+        assert_eq!(surplus, [0x66, 0x6f, 0x6f, 0xff, 0xff, 0xff, 0xff, 0xff]);
+
+        assert_eq!(decode_to_slice_ref("", &mut []), Ok(&mut [][..]));
+    }
+
+    #[test]
+    pub fn test_decode_to_slice_ref_errors() {
+        let mut out = [0u8; 2];
+        assert_eq!(
+            decode_to_slice_ref("666f6f", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_to_slice_ref("666", &mut out),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_to_slice_ref("6g", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "generic-array", feature = "alloc"))]
+    #[allow(deprecated)]
+    pub fn test_from_hex_generic_array() {
+        use generic_array::{typenum::U32, GenericArray};
+
+        let hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let decoded = GenericArray::<u8, U32>::from_hex(hash).unwrap();
+        assert_eq!(decoded.as_slice(), &decode(hash).unwrap()[..]);
+        assert_eq!(decoded.encode_hex::<String>(), hash);
+
+        assert_eq!(
+            GenericArray::<u8, U32>::from_hex(&hash[..62]),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_with_capacity() {
+        let encoded = encode_with_capacity("foobar", 10);
+        assert_eq!(encoded, "666f6f626172");
+        assert!(encoded.capacity() >= 22);
+
+        let encoded = encode_with_capacity("", 3);
+        assert_eq!(encoded, "");
+        assert!(encoded.capacity() >= 3);
+
+        assert_eq!(encode_with_capacity("foobar", 0), encode("foobar"));
+    }
+
+    #[test]
+    pub fn test_decode_to_slice_empty() {
+        assert_eq!(decode_to_slice("", &mut []), Ok(()));
+        assert_eq!(decode_to_slice(b"", &mut [0u8; 0]), Ok(()));
+        assert_eq!(
+            decode_to_slice("", &mut [0u8; 1]),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_to_slice("00", &mut []),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "std"))]
+    pub fn test_decode_os_string() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let decoded = decode_os_string("2f746d702fff6b6977").unwrap();
+        assert!(decoded.to_str().is_none());
+        assert_eq!(decoded.as_bytes(), b"/tmp/\xffkiw");
+        assert_eq!(decode_os_string("666f6f"), Ok("foo".into()));
+        assert_eq!(decode_os_string("2f7"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_first_run() {
+        assert_eq!(
+            decode_first_run("666f6f626172 626172"),
+            Ok((b"foobar".to_vec(), 12))
+        );
+        assert_eq!(decode_first_run("666f6F"), Ok((b"foo".to_vec(), 6)));
+        assert_eq!(decode_first_run(" 666f6f"), Ok((vec![], 0)));
+        assert_eq!(decode_first_run(""), Ok((vec![], 0)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_first_run_odd() {
+        assert_eq!(decode_first_run("666f6 f"), Ok((b"fo".to_vec(), 4)));
+        assert_eq!(decode_first_run("666f6"), Ok((b"fo".to_vec(), 4)));
+        assert_eq!(decode_first_run("6g"), Ok((vec![], 0)));
+    }
+
+    #[test]
+    pub fn test_detect_case() {
+        assert_eq!(detect_case("666f6f626172"), Ok(Some(Case::Lower)));
+        assert_eq!(detect_case("666F6F626172"), Ok(Some(Case::Upper)));
+        assert_eq!(detect_case("666F6f626172"), Ok(None));
+        assert_eq!(detect_case("0123456789"), Ok(None));
+        assert_eq!(detect_case(""), Ok(None));
+        assert_eq!(
+            detect_case("666F6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_array_checked_length() {
+        assert_eq!(
+            decode_array_checked::<3, _>("666f6f626172"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 6,
+                actual: 12
+            })
+        );
+        assert_eq!(
+            decode_array_checked::<1, _>(""),
+            Err(FromHexError::UnexpectedLength {
+                expected: 2,
+                actual: 0
+            })
+        );
+        assert_eq!(decode_array_checked::<0, _>(""), Ok([]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_display() {
+        let bytes = vec![0x00, 0x6b, 0xff, 0x10];
+        assert_eq!(format!("{}", display(&bytes)), encode(&bytes));
+        assert_eq!(format!("{:X}", display(&bytes)), encode_upper(&bytes));
+        assert_eq!(format!("{}", display("foobar")), "666f6f626172");
+        assert_eq!(format!("{}", display(&[0u8; 0])), "");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_display_truncated() {
+        let bytes = b"foobar";
+        assert_eq!(display_truncated(bytes, 12).to_string(), "666f6f626172");
+        assert_eq!(display_truncated(bytes, 100).to_string(), "666f6f626172");
+        assert_eq!(display_truncated(&[0u8; 0], 0).to_string(), "");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    pub fn test_display_truncated_long() {
+        let bytes = vec![0xab; 4096];
+        assert_eq!(
+            display_truncated(&bytes, 8).to_string(),
+            "abababab…(4096 bytes)"
+        );
+        assert_eq!(
+            display_truncated(b"foobar", 5).to_string(),
+            "666f6…(6 bytes)"
+        );
+        assert_eq!(display_truncated(b"foobar", 0).to_string(), "…(6 bytes)");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_rc_arc() {
+        let expected = Vec::from_hex("666f6f626172").unwrap();
+        assert_eq!(
+            &*Rc::<[u8]>::from_hex("666f6f626172").unwrap(),
+            &expected[..]
+        );
+        assert_eq!(
+            &*Arc::<[u8]>::from_hex("666F6f626172").unwrap(),
+            &expected[..]
+        );
+
+        assert_eq!(Rc::<[u8]>::from_hex("666"), Err(FromHexError::OddLength));
+        assert_eq!(
+            Arc::<[u8]>::from_hex("6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_into_vec_at() {
+        let mut out = b"foo".to_vec();
+        decode_into_vec_at("626172", &mut out, 3).unwrap();
+        assert_eq!(out, b"foobar");
+
+        let mut out = b"foobar".to_vec();
+        decode_into_vec_at("4f4f", &mut out, 1).unwrap();
+        assert_eq!(out, b"fOObar");
+
+        let mut out = b"foo".to_vec();
+        decode_into_vec_at("6261", &mut out, 2).unwrap();
+        assert_eq!(out, b"foba");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_into_vec_at_gap() {
+        let mut out = vec![0xff];
+        decode_into_vec_at("abcd", &mut out, 3).unwrap();
+        assert_eq!(out, [0xff, 0, 0, 0xab, 0xcd]);
+
+        let mut out = vec![0xff];
+        assert_eq!(
+            decode_into_vec_at("abc", &mut out, 3),
+            Err(FromHexError::OddLength)
         );
+        assert_eq!(out, [0xff]);
     }
 
     #[test]
-    pub fn test_from_hex_okay_str() {
-        assert_eq!(Vec::from_hex("666f6f626172").unwrap(), b"foobar");
-        assert_eq!(Vec::from_hex("666F6F626172").unwrap(), b"foobar");
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_lower() {
+        assert_eq!(decode_lower("666f6f626172"), Ok(b"foobar".to_vec()));
+        assert_eq!(
+            decode_lower("A0"),
+            Err(FromHexError::WrongCase {
+                c: 'A',
+                index: 0,
+                expected: Case::Lower
+            })
+        );
+        assert_eq!(
+            decode_lower("6g6F"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(decode_lower("a"), Err(FromHexError::OddLength));
     }
 
     #[test]
-    pub fn test_from_hex_okay_bytes() {
-        assert_eq!(Vec::from_hex(b"666f6f626172").unwrap(), b"foobar");
-        assert_eq!(Vec::from_hex(b"666F6F626172").unwrap(), b"foobar");
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_lower_unchecked_case() {
+        assert_eq!(
+            decode_lower_unchecked_case("666f6f626172"),
+            Ok(b"foobar".to_vec())
+        );
+        assert_eq!(
+            decode_lower_unchecked_case("0123456789abcdef"),
+            Ok(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef])
+        );
+        assert_eq!(decode_lower_unchecked_case(""), Ok(vec![]));
+        assert_eq!(
+            decode_lower_unchecked_case("aB"),
+            Err(FromHexError::InvalidHexCharacter { c: 'B', index: 1 })
+        );
+        assert_eq!(
+            decode_lower_unchecked_case("ABCDEF"),
+            Err(FromHexError::InvalidHexCharacter { c: 'A', index: 0 })
+        );
+        assert_eq!(
+            decode_lower_unchecked_case("6g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(
+            decode_lower_unchecked_case("a"),
+            Err(FromHexError::OddLength)
+        );
     }
 
     #[test]
-    pub fn test_invalid_length() {
-        assert_eq!(Vec::from_hex("1").unwrap_err(), FromHexError::OddLength);
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_upper() {
+        assert_eq!(decode_upper("666F6F626172"), Ok(b"foobar".to_vec()));
         assert_eq!(
-            Vec::from_hex("666f6f6261721").unwrap_err(),
-            FromHexError::OddLength
+            decode_upper("666F6f"),
+            Err(FromHexError::WrongCase {
+                c: 'f',
+                index: 5,
+                expected: Case::Upper
+            })
         );
     }
 
     #[test]
-    pub fn test_invalid_char() {
+    #[cfg(feature = "std")]
+    pub fn test_wrong_case_display() {
+        let error = FromHexError::WrongCase {
+            c: 'A',
+            index: 0,
+            expected: Case::Lower,
+        };
         assert_eq!(
-            Vec::from_hex("66ag").unwrap_err(),
-            FromHexError::InvalidHexCharacter { c: 'g', index: 3 }
+            error.to_string(),
+            "Invalid character 'A' at position 0: expected lowercase"
         );
     }
 
     #[test]
-    pub fn test_empty() {
-        assert_eq!(Vec::from_hex("").unwrap(), b"");
+    pub fn test_encode_to_str() {
+        let data: [u8; 16] = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_to_str(&data, &mut buf),
+            "00112233445566778899aabbccddeeff"
+        );
+
+        assert_eq!(encode_to_str(&[], &mut []), "");
     }
 
     #[test]
-    pub fn test_from_hex_whitespace() {
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_in_place() {
+        let hex = b"666f6F626172".to_vec();
+        let capacity = hex.capacity();
+        let decoded = decode_in_place(hex).unwrap();
+        assert_eq!(decoded, b"foobar");
+        assert_eq!(decoded.capacity(), capacity);
+
+        assert_eq!(decode_in_place(vec![]), Ok(vec![]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_in_place_errors() {
         assert_eq!(
-            Vec::from_hex("666f 6f62617").unwrap_err(),
-            FromHexError::InvalidHexCharacter { c: ' ', index: 4 }
+            decode_in_place(b"666".to_vec()),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_in_place(b"666f6g".to_vec()),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
         );
     }
 
     #[test]
-    pub fn test_from_hex_array() {
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_in_place_long() {
+        let data: Vec<u8> = (0..10_000).map(|i| (i * 7) as u8).collect();
+        let hex = encode_upper(&data).into_bytes();
+        let capacity = hex.capacity();
+        let decoded = decode_in_place(hex).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(decoded.capacity(), capacity);
+
+        let mut hex = encode(&data).into_bytes();
+        hex[19_999] = b'x';
         assert_eq!(
-            <[u8; 6] as FromHex>::from_hex("666f6f626172"),
-            Ok([0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72])
+            decode_in_place(hex),
+            Err(FromHexError::InvalidHexCharacter {
+                c: 'x',
+                index: 19_999
+            })
         );
+    }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_from_iter() {
         assert_eq!(
-            <[u8; 5] as FromHex>::from_hex("666f6f626172"),
+            encode_from_iter(0u8..=15),
+            "000102030405060708090a0b0c0d0e0f"
+        );
+        assert_eq!(
+            encode_from_iter(b"foobar".iter().copied()),
+            encode("foobar")
+        );
+        assert_eq!(encode_from_iter(iter::empty()), "");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_describe_error() {
+        let input = "666f6f6g626172";
+        let error = decode(input).unwrap_err();
+        assert_eq!(
+            describe_error(&error, input),
+            "Invalid character 'g' at position 7 (…\"f6f6g6261\"…)"
+        );
+
+        let error = decode("66ag").unwrap_err();
+        assert_eq!(
+            describe_error(&error, "66ag"),
+            "Invalid character 'g' at position 3 (\"66ag\")"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_describe_error_edge_cases() {
+        assert_eq!(
+            describe_error(&FromHexError::OddLength, "666"),
+            "Odd number of digits"
+        );
+
+        let error = FromHexError::InvalidHexCharacter { c: 'x', index: 10 };
+        assert_eq!(
+            describe_error(&error, "66"),
+            "Invalid character 'x' at position 10"
+        );
+
+        let input = "éééé0g";
+        let error = FromHexError::InvalidHexCharacter { c: 'g', index: 9 };
+        assert_eq!(
+            describe_error(&error, input),
+            "Invalid character 'g' at position 9 (…\"éé0g\")"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_le_words() {
+        assert_eq!(
+            decode_le_words("efbeadde", 4),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            decode_le_words("0100000002000000", 4),
+            Ok(vec![0, 0, 0, 1, 0, 0, 0, 2])
+        );
+        assert_eq!(
+            decode_le_words("addeefbe", 2),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(decode_le_words("adde", 1), Ok(vec![0xad, 0xde]));
+        assert_eq!(decode_le_words("", 4), Ok(vec![]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_le_words_errors() {
+        assert_eq!(
+            decode_le_words("efbead", 4),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_le_words("addeef", 2),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(decode_le_words("adde0", 2), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_le_words("adxe", 2),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_skip_prefix_bytes() {
+        let bom = "\u{feff}666f6f626172";
+        assert_eq!(bom.as_bytes()[..3], [0xef, 0xbb, 0xbf]);
+        assert_eq!(decode_skip_prefix_bytes(bom, 3), Ok(b"foobar".to_vec()));
+        assert_eq!(
+            decode_skip_prefix_bytes(b"\x03666f6f", 1),
+            Ok(b"foo".to_vec())
+        );
+        assert_eq!(decode_skip_prefix_bytes("666f", 0), Ok(b"fo".to_vec()));
+        assert_eq!(decode_skip_prefix_bytes("\u{feff}", 3), Ok(vec![]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_skip_prefix_bytes_errors() {
+        assert_eq!(
+            decode_skip_prefix_bytes("\u{feff}66zf", 3),
+            Err(FromHexError::InvalidHexCharacter { c: 'z', index: 5 })
+        );
+        assert_eq!(
+            decode_skip_prefix_bytes("\u{feff}666", 3),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_skip_prefix_bytes("\u{feff}666f", 2),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_skip_prefix_bytes("\u{feff}666", 2),
+            Err(FromHexError::InvalidHexCharacter {
+                c: '\u{bf}',
+                index: 2
+            })
+        );
+        assert_eq!(
+            decode_skip_prefix_bytes("ab", 3),
             Err(FromHexError::InvalidStringLength)
         );
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_iter() {
+        let digits = b"66:6f:6f".iter().copied().filter(|&c| c != b':');
+        assert_eq!(Vec::from_hex_iter(digits), Ok(b"foo".to_vec()));
+        assert_eq!(Vec::from_hex_iter(b"".iter().copied()), Ok(vec![]));
+        assert_eq!(
+            <[u8; 2]>::from_hex_iter(b"6b69".iter().copied()),
+            Ok(*b"ki")
+        );
+        assert_eq!(
+            VecDeque::from_hex_iter(b"6b69".iter().copied()),
+            Ok(VecDeque::from(b"ki".to_vec()))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_from_hex_iter_errors() {
+        let inputs: &[&[u8]] = &[b"6", b"6g", b"g6f", b"6f6g", b"6f6g6", b"\xff\x00"];
+        for input in inputs {
+            assert_eq!(
+                Vec::from_hex_iter(input.iter().copied()),
+                Vec::from_hex(input)
+            );
+        }
+
+        assert_eq!(
+            Vec::from_hex_iter(b"6f6g".iter().copied()),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(
+            Vec::from_hex_iter(b"6g6".iter().copied()),
+            Err(FromHexError::OddLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_base16() {
+        // Test vectors from RFC 4648, section 10.
+        let vectors = [
+            ("", ""),
+            ("f", "66"),
+            ("fo", "666F"),
+            ("foo", "666F6F"),
+            ("foob", "666F6F62"),
+            ("fooba", "666F6F6261"),
+            ("foobar", "666F6F626172"),
+        ];
+        for (data, encoded) in &vectors {
+            assert_eq!(base16_encode(data), *encoded);
+            assert_eq!(base16_decode(encoded, false), Ok(data.as_bytes().to_vec()));
+            assert_eq!(base16_decode(encoded, true), Ok(data.as_bytes().to_vec()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_base16_decode_lowercase() {
+        assert_eq!(
+            base16_decode("666f6F", false),
+            Err(FromHexError::WrongCase {
+                c: 'f',
+                index: 3,
+                expected: Case::Upper
+            })
+        );
+        assert_eq!(base16_decode("666f6F", true), Ok(b"foo".to_vec()));
+        assert_eq!(base16_decode("66G", true), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    pub fn test_decode_array_with_separator() {
+        assert_eq!(
+            decode_array_with_separator("de:ad:be:ef", ':'),
+            Ok([0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            decode_array_with_separator("00-1A-2b-3C-4d-5E", '-'),
+            Ok([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e])
+        );
+        assert_eq!(decode_array_with_separator("dead", ':'), Ok([0xde, 0xad]));
+        assert_eq!(
+            decode_array_with_separator(":d::ead:", ':'),
+            Ok([0xde, 0xad])
+        );
+        assert_eq!(
+            decode_array_with_separator("de\u{b7}ad", '\u{b7}'),
+            Ok([0xde, 0xad])
+        );
+        assert_eq!(decode_array_with_separator("::", ':'), Ok([]));
+    }
+
+    #[test]
+    pub fn test_decode_array_with_separator_errors() {
+        assert_eq!(
+            decode_array_with_separator::<4, _>("de:ad:be", ':'),
+            Err(FromHexError::UnexpectedLength {
+                expected: 8,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            decode_array_with_separator::<2, _>("de:ad:be", ':'),
+            Err(FromHexError::UnexpectedLength {
+                expected: 4,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            decode_array_with_separator::<2, _>("de:a", ':'),
+            Err(FromHexError::UnexpectedLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            decode_array_with_separator::<2, _>("de-ad", ':'),
+            Err(FromHexError::UnexpectedLength {
+                expected: 4,
+                actual: 5
+            })
+        );
+        assert_eq!(
+            decode_array_with_separator::<2, _>("de:ag", ':'),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+        assert_eq!(
+            decode_array_with_separator::<2, _>("de-a", ':'),
+            Err(FromHexError::InvalidHexCharacter { c: '-', index: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_opt() {
+        assert_eq!(encode_opt(Some("foobar")), "666f6f626172");
+        assert_eq!(encode_opt(Some([0xffu8, 0x00])), "ff00");
+        assert_eq!(encode_opt(Some("")), "");
+        assert_eq!(encode_opt(None::<Vec<u8>>), "");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_opt() {
+        assert_eq!(decode_opt(Some("666f6f626172")), Ok(b"foobar".to_vec()));
+        assert_eq!(decode_opt(Some("")), Ok(vec![]));
+        assert_eq!(decode_opt(None::<&str>), Ok(vec![]));
+        assert_eq!(decode_opt(Some("6")), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_opt(Some(encode_opt(Some("kiwi")))),
+            Ok(b"kiwi".to_vec())
+        );
+    }
+
+    #[test]
+    pub fn test_decode_and_ct_eq() {
+        let expected = b"foobar";
+        assert_eq!(decode_and_ct_eq("666f6f626172", expected), Ok(true));
+        assert_eq!(decode_and_ct_eq("666F6F626172", expected), Ok(true));
+        assert_eq!(decode_and_ct_eq("", b""), Ok(true));
+
+        assert_eq!(decode_and_ct_eq("766f6f626172", expected), Ok(false));
+        assert_eq!(decode_and_ct_eq("666f6f626173", expected), Ok(false));
+        assert_eq!(decode_and_ct_eq("666f6f6261", expected), Ok(false));
+        assert_eq!(decode_and_ct_eq("666f6f62617200", expected), Ok(false));
+        assert_eq!(decode_and_ct_eq("", expected), Ok(false));
+        assert_eq!(decode_and_ct_eq("00", b""), Ok(false));
+    }
+
+    #[test]
+    pub fn test_decode_and_ct_eq_errors() {
+        assert_eq!(
+            decode_and_ct_eq("666f6f62617", b"foobar"),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_and_ct_eq("666f6g626172", b"foobar"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+        assert_eq!(
+            decode_and_ct_eq("666f6g", b"foobar"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_iter_adapter() {
+        let bytes = DecodeIter::new(b"666f6F626172".iter().copied());
+        assert!(bytes.eq(b"foobar".iter().map(|&byte| Ok(byte))));
+        assert_eq!(DecodeIter::new(b"".iter().copied()).next(), None);
+
+        let digits = b"66:6f:6f".iter().copied().filter(|&c| c != b':');
+        assert!(DecodeIter::new(digits).eq(b"foo".iter().map(|&byte| Ok(byte))));
+    }
+
+    #[test]
+    pub fn test_decode_iter_adapter_errors() {
+        let mut iter = DecodeIter::new(b"666g6f62".iter().copied());
+        assert_eq!(iter.next(), Some(Ok(0x66)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = DecodeIter::new(b"66f".iter().copied());
+        assert_eq!(iter.next(), Some(Ok(0x66)));
+        assert_eq!(iter.next(), Some(Err(FromHexError::OddLength)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = DecodeIter::new(b"66x".iter().copied());
+        assert_eq!(iter.next(), Some(Ok(0x66)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    pub fn test_decode_iter_adapter_size_hint() {
+        let mut iter = DecodeIter::new(b"66f".iter().copied());
+        assert_eq!(iter.size_hint(), (0, Some(2)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(1)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    pub fn test_decode_to_slice_strict() {
+        let mut bytes = [0u8; 4];
+        assert_eq!(decode_to_slice_strict("6b697769", &mut bytes), Ok(()));
+        assert_eq!(&bytes, b"kiwi");
+        assert_eq!(decode_to_slice_strict("", &mut []), Ok(()));
+        assert_eq!(
+            decode_to_slice_strict("6b69776g", &mut bytes),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 7 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_to_slice_strict_lengths() {
+        let mut bytes = [0u8; 4];
+        // Too long, e.g. a field followed by more data.
+        assert_eq!(
+            decode_to_slice_strict("6b6977690a", &mut bytes),
+            Err(FromHexError::UnexpectedLength {
+                expected: 8,
+                actual: 10
+            })
+        );
+        assert_eq!(
+            decode_to_slice_strict("6b6977690", &mut bytes),
+            Err(FromHexError::UnexpectedLength {
+                expected: 8,
+                actual: 9
+            })
+        );
+
+        // Too short.
+        assert_eq!(
+            decode_to_slice_strict("6b6977", &mut bytes),
+            Err(FromHexError::UnexpectedLength {
+                expected: 8,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            decode_to_slice_strict("6b69776", &mut bytes),
+            Err(FromHexError::UnexpectedLength {
+                expected: 8,
+                actual: 7
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_encode_with_case_mask() {
+        let data = [0x0a, 0xbc, 0xde, 0xf1];
+        assert_eq!(encode_with_case_mask(data, &[false; 8]), "0abcdef1");
+        assert_eq!(encode_with_case_mask(data, &[true; 8]), "0ABCDEF1");
+
+        let alternating: Vec<bool> = (0..8).map(|i| i % 2 == 1).collect();
+        assert_eq!(encode_with_case_mask(data, &alternating), "0AbCdEf1");
+        assert_eq!(encode_with_case_mask(b"", &[]), "");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "case mask length must be twice the data length")]
+    pub fn test_encode_with_case_mask_wrong_length() {
+        let _ = encode_with_case_mask([0xab], &[true]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_checking_case() {
+        let bytes = vec![0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(
+            decode_checking_case("deadbeef"),
+            Ok((bytes.clone(), Some(Case::Lower)))
+        );
+        assert_eq!(
+            decode_checking_case("DEADBEEF"),
+            Ok((bytes.clone(), Some(Case::Upper)))
+        );
+        assert_eq!(decode_checking_case("DeadBeef"), Ok((bytes, None)));
+        assert_eq!(decode_checking_case("0123"), Ok((vec![0x01, 0x23], None)));
+        assert_eq!(decode_checking_case(""), Ok((vec![], None)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    pub fn test_decode_checking_case_errors() {
+        assert_eq!(decode_checking_case("abc"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_checking_case("abCG"),
+            Err(FromHexError::InvalidHexCharacter { c: 'G', index: 3 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_fields() {
+        let mut data = [0u8; 36];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut hex = [0u8; 72];
+        encode_to_slice(data, &mut hex).unwrap();
+
+        let (header, body) = decode_fields::<4, 32, _>(&hex[..]).unwrap();
+        assert_eq!(header, [0, 1, 2, 3]);
+        assert_eq!(body[..], data[4..]);
+
+        assert_eq!(decode_fields::<0, 2, _>("6b69"), Ok(([], *b"ki")));
+        assert_eq!(decode_fields::<2, 0, _>("6b69"), Ok((*b"ki", [])));
+    }
+
+    #[test]
+    pub fn test_decode_fields_errors() {
+        assert_eq!(
+            decode_fields::<1, 2, _>("6b69"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 6,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            decode_fields::<1, 2, _>("6b697769"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 6,
+                actual: 8
+            })
+        );
+        assert_eq!(
+            decode_fields::<1, 2, _>("6g6977"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(
+            decode_fields::<1, 2, _>("6b69g7"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+    }
 }
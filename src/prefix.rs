@@ -0,0 +1,116 @@
+//! Decoding hex strings tagged with a known byte prefix.
+
+use crate::{decode, FromHexError};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The error type for decoding a hex string with an expected prefix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecodePrefixError {
+    /// The input isn't a valid hex string.
+    Hex(FromHexError),
+
+    /// The input is valid hex, but the decoded bytes don't start with the
+    /// expected prefix.
+    PrefixMismatch,
+}
+
+impl From<FromHexError> for DecodePrefixError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodePrefixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Hex(error) => Some(error),
+            Self::PrefixMismatch => None,
+        }
+    }
+}
+
+impl fmt::Display for DecodePrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Hex(error) => write!(f, "{}", error),
+            Self::PrefixMismatch => write!(f, "Decoded bytes don't start with the expected prefix"),
+        }
+    }
+}
+
+/// Decodes a hex string into raw bytes, checking that they start with
+/// `prefix` and returning the bytes following it.
+///
+/// This is useful for tagged values such as versioned keys. Input which
+/// decodes to fewer bytes than `prefix` is a mismatch as well.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_expect_prefix("016b697769", &[0x01]), Ok(b"kiwi".to_vec()));
+/// assert_eq!(
+///     hex::decode_expect_prefix("026b697769", &[0x01]),
+///     Err(hex::DecodePrefixError::PrefixMismatch)
+/// );
+/// ```
+pub fn decode_expect_prefix<T: AsRef<[u8]>>(
+    data: T,
+    prefix: &[u8],
+) -> Result<Vec<u8>, DecodePrefixError> {
+    let mut decoded = decode(data)?;
+    if !decoded.starts_with(prefix) {
+        return Err(DecodePrefixError::PrefixMismatch);
+    }
+
+    decoded.drain(..prefix.len());
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_decode_expect_prefix() {
+        assert_eq!(
+            decode_expect_prefix("cafe666f6f", &[0xca, 0xfe]),
+            Ok(b"foo".to_vec())
+        );
+        assert_eq!(decode_expect_prefix("cafe", &[0xca, 0xfe]), Ok(vec![]));
+        assert_eq!(decode_expect_prefix("666f6f", &[]), Ok(b"foo".to_vec()));
+    }
+
+    #[test]
+    fn test_decode_expect_prefix_mismatch() {
+        assert_eq!(
+            decode_expect_prefix("cafd666f6f", &[0xca, 0xfe]),
+            Err(DecodePrefixError::PrefixMismatch)
+        );
+        assert_eq!(
+            decode_expect_prefix("ca", &[0xca, 0xfe]),
+            Err(DecodePrefixError::PrefixMismatch)
+        );
+        assert_eq!(
+            decode_expect_prefix("", &[0xca]),
+            Err(DecodePrefixError::PrefixMismatch)
+        );
+    }
+
+    #[test]
+    fn test_decode_expect_prefix_invalid_hex() {
+        assert_eq!(
+            decode_expect_prefix("cafe6", &[0xca, 0xfe]),
+            Err(DecodePrefixError::Hex(FromHexError::OddLength))
+        );
+        assert_eq!(
+            decode_expect_prefix("cage", &[0xca]),
+            Err(DecodePrefixError::Hex(FromHexError::InvalidHexCharacter {
+                c: 'g',
+                index: 2
+            }))
+        );
+    }
+}
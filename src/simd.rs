@@ -0,0 +1,38 @@
+//! Accelerated encoding and decoding of whole blocks.
+//!
+//! The functions here process as much of the input as they can in blocks and
+//! return how far they got, leaving the rest, including any error reporting,
+//! to the scalar code. With the `simd` feature, aarch64 uses NEON. Other
+//! targets decode eight digits at a time in a `u64`, and don't accelerate
+//! encoding.
+
+#[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+#[cfg(not(all(feature = "simd", target_arch = "aarch64", target_feature = "neon")))]
+mod swar;
+
+#[cfg(all(
+    feature = "alloc",
+    feature = "simd",
+    target_arch = "aarch64",
+    target_feature = "neon"
+))]
+pub(crate) use self::neon::validate_prefix;
+#[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))]
+pub(crate) use self::neon::{decode_prefix, encode_prefix};
+#[cfg(not(all(feature = "simd", target_arch = "aarch64", target_feature = "neon")))]
+pub(crate) use self::swar::decode_prefix;
+#[cfg(all(
+    feature = "alloc",
+    feature = "simd",
+    not(all(target_arch = "aarch64", target_feature = "neon"))
+))]
+pub(crate) use self::swar::validate_prefix;
+
+/// Encodes a prefix of `source` into the corresponding prefix of `output`,
+/// returning the number of bytes of `source` encoded.
+#[cfg(not(all(feature = "simd", target_arch = "aarch64", target_feature = "neon")))]
+#[inline(always)]
+pub(crate) fn encode_prefix(_table: &crate::HexPairs, _source: &[u8], _output: &mut [u8]) -> usize {
+    0
+}
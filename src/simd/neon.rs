@@ -0,0 +1,95 @@
+//! NEON implementation for aarch64, where NEON is always available.
+
+use core::arch::aarch64::*;
+
+use crate::HexPairs;
+
+/// Encodes blocks of 16 bytes by looking up both digits of each byte in a
+/// 16 byte table and storing them interleaved.
+pub(crate) fn encode_prefix(table: &HexPairs, source: &[u8], output: &mut [u8]) -> usize {
+    assert!(output.len() >= source.len() * 2);
+
+    let mut chars = [0u8; 16];
+    for (c, pair) in chars.iter_mut().zip(table.iter()) {
+        *c = pair[1];
+    }
+
+    let blocks = source.len() / 16;
+    // SAFETY: NEON is enabled, and the assertion above ensures that all
+    // reads and writes are in bounds.
+    unsafe {
+        let chars = vld1q_u8(chars.as_ptr());
+        let low_mask = vdupq_n_u8(0x0f);
+        for i in 0..blocks {
+            let bytes = vld1q_u8(source.as_ptr().add(16 * i));
+            let high = vqtbl1q_u8(chars, vshrq_n_u8::<4>(bytes));
+            let low = vqtbl1q_u8(chars, vandq_u8(bytes, low_mask));
+            vst2q_u8(output.as_mut_ptr().add(32 * i), uint8x16x2_t(high, low));
+        }
+    }
+
+    blocks * 16
+}
+
+/// Decodes blocks of 32 characters, loading the high and low digits into
+/// separate vectors. Stops at the first block containing an invalid
+/// character, so that the scalar code can report it.
+pub(crate) fn decode_prefix(data: &[u8], out: &mut [u8]) -> usize {
+    assert!(data.len() >= out.len() * 2);
+
+    let blocks = out.len() / 16;
+    // SAFETY: NEON is enabled, and the assertion above ensures that all
+    // reads and writes are in bounds.
+    unsafe {
+        for i in 0..blocks {
+            let digits = vld2q_u8(data.as_ptr().add(32 * i));
+            let (high, high_valid) = digit_values(digits.0);
+            let (low, low_valid) = digit_values(digits.1);
+            if vminvq_u8(vandq_u8(high_valid, low_valid)) == 0 {
+                return 16 * i;
+            }
+            vst1q_u8(
+                out.as_mut_ptr().add(16 * i),
+                vorrq_u8(vshlq_n_u8::<4>(high), low),
+            );
+        }
+    }
+
+    blocks * 16
+}
+
+/// Checks blocks of 16 characters, returning the length of the prefix found
+/// to consist of valid hex digits.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate_prefix(data: &[u8]) -> usize {
+    let blocks = data.len() / 16;
+    // SAFETY: NEON is enabled, and all reads are in bounds.
+    unsafe {
+        for i in 0..blocks {
+            let (_, valid) = digit_values(vld1q_u8(data.as_ptr().add(16 * i)));
+            if vminvq_u8(valid) == 0 {
+                return 16 * i;
+            }
+        }
+    }
+
+    blocks * 16
+}
+
+/// Returns the value of each hex digit in `chars`, and a mask which is set
+/// for the valid ones.
+#[inline(always)]
+unsafe fn digit_values(chars: uint8x16_t) -> (uint8x16_t, uint8x16_t) {
+    // Subtracting '0' maps '0'...'9' to 0...9 and everything else outside of
+    // that range, thanks to wrapping.
+    let digits = vsubq_u8(chars, vdupq_n_u8(b'0'));
+    let is_digit = vcltq_u8(digits, vdupq_n_u8(10));
+
+    // Setting 0x20 maps 'A'...'F' to 'a'...'f', which subtracting 'a' then
+    // maps to 0...5.
+    let letters = vsubq_u8(vorrq_u8(chars, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+    let is_letter = vcltq_u8(letters, vdupq_n_u8(6));
+
+    let values = vbslq_u8(is_digit, digits, vaddq_u8(letters, vdupq_n_u8(10)));
+    (values, vorrq_u8(is_digit, is_letter))
+}
@@ -0,0 +1,160 @@
+//! Portable decoding of eight digits at a time within a `u64` ("SIMD within a
+//! register").
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = ONES * 0x80;
+
+/// Sets the high bit of each byte of `x` which is greater than `low` and less
+/// than `high`, and clears all other bits. Bytes of `0x80` and above never
+/// match. Requires `low < 0x80` and `high <= 0x80`.
+#[inline(always)]
+fn between(x: u64, low: u64, high: u64) -> u64 {
+    let low7 = x & (ONES * 0x7f);
+    (ONES * (0x7f + high)).wrapping_sub(low7) & !x & (low7 + ONES * (0x7f - low)) & HIGH_BITS
+}
+
+/// Checks whether all eight characters in `chars` are hex digits.
+#[inline(always)]
+fn is_valid_block(chars: u64) -> bool {
+    let digits = between(chars, b'/'.into(), b':'.into());
+    let letters = between(chars | (ONES * 0x20), b'`'.into(), b'g'.into());
+    digits | letters == HIGH_BITS
+}
+
+/// Decodes the eight digits in `chars`, loaded in little endian order, into
+/// four bytes, or returns `None` if any of them is invalid.
+#[inline(always)]
+fn decode_block(chars: u64) -> Option<u32> {
+    if !is_valid_block(chars) {
+        return None;
+    }
+
+    // The low nibble of a digit is its value, and that of a letter is its
+    // value minus nine. Only letters have the 0x40 bit set.
+    let values = (chars & (ONES * 0x0f)) + ((chars >> 6) & ONES) * 9;
+
+    // Combine each value at an even position with the following one, then
+    // gather the bytes at even positions.
+    let pairs = ((values << 4) | (values >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let pairs = (pairs | (pairs >> 8)) & 0x0000_ffff_0000_ffff;
+    Some((pairs | (pairs >> 16)) as u32)
+}
+
+/// Decodes blocks of eight digits. Stops at the first block containing an
+/// invalid character, so that the scalar code can report it.
+pub(crate) fn decode_prefix(data: &[u8], out: &mut [u8]) -> usize {
+    let mut done = 0;
+    for (chars, bytes) in data.chunks_exact(8).zip(out.chunks_exact_mut(4)) {
+        let mut block = [0; 8];
+        block.copy_from_slice(chars);
+        match decode_block(u64::from_le_bytes(block)) {
+            Some(decoded) => bytes.copy_from_slice(&decoded.to_le_bytes()),
+            None => break,
+        }
+        done += 4;
+    }
+
+    done
+}
+
+/// Checks blocks of eight characters, returning the length of the prefix
+/// found to consist of valid hex digits.
+#[cfg(all(feature = "alloc", feature = "simd"))]
+pub(crate) fn validate_prefix(data: &[u8]) -> usize {
+    data.chunks_exact(8)
+        .take_while(|chars| {
+            let mut block = [0; 8];
+            block.copy_from_slice(chars);
+            is_valid_block(u64::from_le_bytes(block))
+        })
+        .count()
+        * 8
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_prefix() {
+        let data = b"00112233445566778899aAbBcCdDeEfF0a";
+        let mut out = [0; 17];
+        assert_eq!(decode_prefix(data, &mut out), 16);
+        assert_eq!(
+            out[..16],
+            [
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_prefix_all_digits() {
+        for c in 0..=255u8 {
+            let mut data = *b"0123abcd";
+            data[3] = c;
+            let mut out = [0; 4];
+            let done = decode_prefix(&data, &mut out);
+            if crate::is_hex_digit(c) {
+                assert_eq!(done, 4);
+                assert_eq!(out[1], 0x20 | crate::val(c, 0).unwrap());
+            } else {
+                assert_eq!(done, 0, "{:#04x} accepted", c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_prefix_invalid_positions() {
+        for index in 0..16 {
+            for &c in b"/:@G`g\x00\x80\xc6\xff" {
+                let mut data = *b"0123456789abcdef";
+                data[index] = c;
+                let mut out = [0; 8];
+                assert_eq!(decode_prefix(&data, &mut out), index / 8 * 4);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "simd"))]
+    fn test_validate_prefix() {
+        let data = b"00112233445566778899aAbBcCdDeEfF0a";
+        assert_eq!(validate_prefix(data), 32);
+        for index in 0..data.len() {
+            let mut data = *data;
+            data[index] = b'g';
+            assert_eq!(validate_prefix(&data), (index / 8 * 8).min(32));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod prop {
+        use super::*;
+        use crate::val;
+        use proptest::prelude::*;
+
+        fn digits() -> impl Strategy<Value = Vec<u8>> {
+            let alphabet = b"0123456789abcdefABCDEF/:@`gG\x00\xff".to_vec();
+            proptest::collection::vec(proptest::sample::select(alphabet), 0..64)
+        }
+
+        proptest! {
+            #[test]
+            fn test_decode_prefix_matches_scalar(data in digits()) {
+                let valid = data
+                    .chunks_exact(2)
+                    .take_while(|pair| val(pair[0], 0).is_ok() && val(pair[1], 1).is_ok())
+                    .count();
+                let mut out = [0; 32];
+                let done = decode_prefix(&data, &mut out);
+                prop_assert_eq!(done, valid / 4 * 4);
+                for (i, byte) in out[..done].iter().enumerate() {
+                    let expected = val(data[2 * i], 0).unwrap() << 4 | val(data[2 * i + 1], 0).unwrap();
+                    prop_assert_eq!(*byte, expected);
+                }
+            }
+        }
+    }
+}
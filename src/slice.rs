@@ -0,0 +1,122 @@
+//! A borrowed view of a byte slice as hex string.
+
+#[cfg(feature = "alloc")]
+use crate::{decode, FromHexError};
+use crate::{write_to_fmt, HEX_PAIRS_LOWER, HEX_PAIRS_UPPER};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Views a byte slice as hex string without allocating.
+///
+/// `Display` and `LowerHex` write the bytes as lowercase hex string, and
+/// `UpperHex` as uppercase one, which makes it easy to log bytes as hex.
+///
+/// # Example
+///
+/// ```
+/// use hex::HexSlice;
+///
+/// let hex = HexSlice::new(b"kiwi");
+/// assert_eq!(hex.to_string(), "6b697769");
+/// assert_eq!(format!("{:X}", hex), "6B697769");
+/// assert_eq!(hex.len(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexSlice<'a>(&'a [u8]);
+
+impl<'a> HexSlice<'a> {
+    /// Creates a view of `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+
+    /// Returns the viewed bytes.
+    pub fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the number of hex characters the bytes are displayed as.
+    pub fn len(self) -> usize {
+        self.0.len() * 2
+    }
+
+    /// Returns `true` if there are no bytes.
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decodes the viewed bytes, if they are a hex string themselves.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(hex::HexSlice::new(b"6b697769").decode(), Ok(b"kiwi".to_vec()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode(self) -> Result<Vec<u8>, FromHexError> {
+        decode(self.0)
+    }
+}
+
+impl<'a> From<&'a [u8]> for HexSlice<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+}
+
+impl fmt::Display for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_to_fmt(&HEX_PAIRS_LOWER, self.0, f)
+    }
+}
+
+impl fmt::LowerHex for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_to_fmt(&HEX_PAIRS_LOWER, self.0, f)
+    }
+}
+
+impl fmt::UpperHex for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_to_fmt(&HEX_PAIRS_UPPER, self.0, f)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test {
+    use super::*;
+    use crate::encode;
+    use alloc::format;
+
+    #[test]
+    fn test_display() {
+        let hex = HexSlice::new(b"foobar");
+        assert_eq!(format!("{}", hex), "666f6f626172");
+        assert_eq!(format!("{:x}", hex), "666f6f626172");
+        assert_eq!(format!("{}", HexSlice::new(&[])), "");
+    }
+
+    #[test]
+    fn test_upper_hex() {
+        let hex = HexSlice::from(&[0xca, 0xfe, 0x0a][..]);
+        assert_eq!(format!("{:X}", hex), "CAFE0A");
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(HexSlice::new(b"foobar").len(), 12);
+        assert!(HexSlice::new(b"").is_empty());
+        assert!(!HexSlice::new(b"f").is_empty());
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let encoded = encode("foobar");
+        let hex = HexSlice::new(encoded.as_bytes());
+        assert_eq!(hex.decode(), Ok(b"foobar".to_vec()));
+        assert_eq!(hex.as_bytes(), b"666f6f626172");
+        assert!(HexSlice::new(b"666").decode().is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Validation of the digit tables used for encoding.
+
+use core::fmt;
+
+/// The error type for validating a table of hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidTableError {
+    /// The symbol at `index` isn't an ASCII character, so encoded strings
+    /// wouldn't be valid UTF-8.
+    NonAscii { index: usize },
+
+    /// The symbol at `index` is the same as an earlier one, so encoded
+    /// strings couldn't be decoded unambiguously.
+    Duplicate { index: usize },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidTableError {}
+
+impl fmt::Display for InvalidTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::NonAscii { index } => write!(f, "Non-ASCII symbol at index {}", index),
+            Self::Duplicate { index } => write!(f, "Duplicate symbol at index {}", index),
+        }
+    }
+}
+
+/// Checks that `table` consists of 16 distinct ASCII characters, and so can
+/// be used to encode the values `0` to `15`.
+///
+/// The built-in lowercase and uppercase tables are checked at compile time.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::validate_table(b"0123456789abcdef"), Ok(()));
+/// assert_eq!(
+///     hex::validate_table(b"0123456789abcdea"),
+///     Err(hex::InvalidTableError::Duplicate { index: 15 })
+/// );
+/// ```
+pub const fn validate_table(table: &[u8; 16]) -> Result<(), InvalidTableError> {
+    let mut index = 0;
+    while index < table.len() {
+        if !table[index].is_ascii() {
+            return Err(InvalidTableError::NonAscii { index });
+        }
+
+        let mut earlier = 0;
+        while earlier < index {
+            if table[earlier] == table[index] {
+                return Err(InvalidTableError::Duplicate { index });
+            }
+            earlier += 1;
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_table() {
+        assert_eq!(validate_table(b"0123456789abcdef"), Ok(()));
+        assert_eq!(validate_table(b"0123456789ABCDEF"), Ok(()));
+        assert_eq!(validate_table(b"ghijklmnopqrstuv"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_table_duplicate() {
+        assert_eq!(
+            validate_table(b"0123456789abcdeA"),
+            Ok(()),
+            "symbols are case sensitive"
+        );
+        assert_eq!(
+            validate_table(b"0023456789abcdef"),
+            Err(InvalidTableError::Duplicate { index: 1 })
+        );
+        assert_eq!(
+            validate_table(b"0123456789abcde0"),
+            Err(InvalidTableError::Duplicate { index: 15 })
+        );
+    }
+
+    #[test]
+    fn test_validate_table_non_ascii() {
+        assert_eq!(
+            validate_table(b"0123456789abcde\xff"),
+            Err(InvalidTableError::NonAscii { index: 15 })
+        );
+        assert_eq!(
+            validate_table(b"\x80\x80\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"),
+            Err(InvalidTableError::NonAscii { index: 0 })
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Decoding hex strings which encode UTF-8 text.
+
+use crate::{decode, FromHexError};
+use alloc::string::String;
+use core::{fmt, str::Utf8Error};
+
+/// The error type for decoding a hex string into text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecodeStringError {
+    /// The input isn't a valid hex string.
+    Hex(FromHexError),
+
+    /// The input is valid hex, but the decoded bytes aren't valid UTF-8.
+    /// `Utf8Error::valid_up_to()` is the offset into the decoded bytes at
+    /// which the invalid sequence starts.
+    Utf8(Utf8Error),
+}
+
+impl From<FromHexError> for DecodeStringError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeStringError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Hex(error) => Some(error),
+            Self::Utf8(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for DecodeStringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Hex(error) => write!(f, "{}", error),
+            Self::Utf8(error) => write!(
+                f,
+                "Decoded bytes aren't valid UTF-8 at offset {}",
+                error.valid_up_to()
+            ),
+        }
+    }
+}
+
+/// Decodes a hex string into UTF-8 text.
+///
+/// This is the inverse of `encode()` on a `str`. Unlike `String::from_hex()`,
+/// the error tells hex errors apart from UTF-8 errors.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_to_string("6b697769"), Ok("kiwi".to_string()));
+///
+/// match hex::decode_to_string("6b69ff") {
+///     Err(hex::DecodeStringError::Utf8(error)) => assert_eq!(error.valid_up_to(), 2),
+///     result => panic!("unexpected result: {:?}", result),
+/// }
+/// ```
+pub fn decode_to_string<T: AsRef<[u8]>>(data: T) -> Result<String, DecodeStringError> {
+    String::from_utf8(decode(data)?).map_err(|error| DecodeStringError::Utf8(error.utf8_error()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encode;
+
+    #[test]
+    fn test_decode_to_string() {
+        for text in &["", "kiwi", "grüße", "日本語"] {
+            assert_eq!(decode_to_string(encode(text)).as_deref(), Ok(*text));
+        }
+    }
+
+    #[test]
+    fn test_decode_to_string_invalid_utf8() {
+        match decode_to_string("6b69e697") {
+            Err(DecodeStringError::Utf8(error)) => {
+                assert_eq!(error.valid_up_to(), 2);
+                assert_eq!(error.error_len(), None);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_decode_to_string_invalid_hex() {
+        assert_eq!(
+            decode_to_string("6b6"),
+            Err(DecodeStringError::Hex(FromHexError::OddLength))
+        );
+        assert_eq!(
+            decode_to_string("ffzz"),
+            Err(DecodeStringError::Hex(FromHexError::InvalidHexCharacter {
+                c: 'z',
+                index: 2
+            }))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Conversions between UUIDs and their 32 digit hex representation.
+
+#[cfg(feature = "alloc")]
+use crate::encode;
+use crate::{val, FromHexError};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use uuid::Uuid;
+
+/// Encodes a UUID as a lowercase hex string of 32 digits, without dashes.
+///
+/// # Example
+///
+/// ```
+/// let uuid = uuid::Uuid::from_bytes(*b"\x67\xe5\x50\x44\x10\xb1\x42\x6f\x92\x47\xbb\x68\x0e\x5f\xe0\xc8");
+/// assert_eq!(hex::encode_uuid(&uuid), "67e5504410b1426f9247bb680e5fe0c8");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_uuid(uuid: &Uuid) -> String {
+    encode(uuid.as_bytes())
+}
+
+/// Decodes a UUID from a hex string of 32 digits, ignoring any dashes.
+///
+/// This accepts both the plain form and the hyphenated form (e.g.
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`). Indices in errors refer to
+/// positions in `s`, including dashes.
+///
+/// # Example
+///
+/// ```
+/// let uuid = hex::decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+/// assert_eq!(hex::decode_uuid("67e5504410b1426f9247bb680e5fe0c8"), Ok(uuid));
+/// ```
+pub fn decode_uuid(s: &str) -> Result<Uuid, FromHexError> {
+    let mut bytes = [0u8; 16];
+    let mut digits = 0;
+    for (index, c) in s.bytes().enumerate() {
+        if c == b'-' {
+            continue;
+        }
+
+        let value = val(c, index)?;
+        if let Some(byte) = bytes.get_mut(digits / 2) {
+            *byte |= value << (if digits % 2 == 0 { 4 } else { 0 });
+        }
+        digits += 1;
+    }
+
+    if digits != 32 {
+        return Err(FromHexError::UnexpectedLength {
+            expected: 32,
+            actual: digits,
+        });
+    }
+
+    Ok(Uuid::from_bytes(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BYTES: [u8; 16] = [
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ];
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_uuid() {
+        let uuid = Uuid::from_bytes(BYTES);
+        assert_eq!(encode_uuid(&uuid), "67e5504410b1426f9247bb680e5fe0c8");
+        assert_eq!(decode_uuid(&encode_uuid(&uuid)), Ok(uuid));
+    }
+
+    #[test]
+    fn test_decode_uuid() {
+        let uuid = Uuid::from_bytes(BYTES);
+        assert_eq!(decode_uuid("67e5504410b1426f9247bb680e5fe0c8"), Ok(uuid));
+        assert_eq!(
+            decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Ok(uuid)
+        );
+        assert_eq!(
+            decode_uuid("67E55044-10B1-426F-9247-BB680E5FE0C8"),
+            Ok(uuid)
+        );
+    }
+
+    #[test]
+    fn test_decode_uuid_invalid() {
+        assert_eq!(
+            decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 32,
+                actual: 30
+            })
+        );
+        assert_eq!(
+            decode_uuid("67e5504410b1426f9247bb680e5fe0c800"),
+            Err(FromHexError::UnexpectedLength {
+                expected: 32,
+                actual: 34
+            })
+        );
+        assert_eq!(
+            decode_uuid("67e55044-10b1-426f-9247-bb680e5fe0cg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 35 })
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Checks which code paths allocate, using a global allocator counting the
+//! allocations made. This file must only contain a single test, as tests run
+//! in parallel.
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn decode_limited() {
+    let data = "00".repeat(1024);
+
+    assert_eq!(
+        allocations(|| assert!(hex::decode_limited(&data, 1023).is_err())),
+        0
+    );
+    assert_eq!(
+        allocations(|| assert!(hex::decode_limited(&data, 1024).is_ok())),
+        1
+    );
+}
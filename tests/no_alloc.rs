@@ -0,0 +1,79 @@
+//! The slice based API has to be usable without `std` or `alloc`, so this
+//! crate doesn't use either. Run with `--no-default-features` to check that
+//! `hex` itself builds without them.
+#![no_std]
+
+use core::fmt;
+use hex::{FromHex, FromHexError, ToHex};
+
+/// A `fmt::Write` sink with a fixed capacity, like `heapless::String`.
+struct FixedString {
+    buffer: [u8; 8],
+    len: usize,
+}
+
+impl FixedString {
+    fn new() -> Self {
+        FixedString {
+            buffer: [0; 8],
+            len: 0,
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl fmt::Write for FixedString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn encode_to_slice() {
+    let mut output = [0u8; 8];
+    hex::encode_to_slice(b"kiwi", &mut output).unwrap();
+    assert_eq!(&output, b"6b697769");
+
+    hex::encode_upper_to_slice([0xca, 0xfe, 0xba, 0xbe], &mut output).unwrap();
+    assert_eq!(&output, b"CAFEBABE");
+}
+
+#[test]
+fn encode_hex_to_fmt() {
+    let mut output = FixedString::new();
+    b"kiwi".encode_hex_to_fmt(&mut output).unwrap();
+    assert_eq!(output.as_bytes(), b"6b697769");
+
+    let mut output = FixedString::new();
+    [0xca, 0xfe].encode_hex_upper_to_fmt(&mut output).unwrap();
+    assert_eq!(output.as_bytes(), b"CAFE");
+
+    let mut output = FixedString::new();
+    assert_eq!(b"kiwis".encode_hex_to_fmt(&mut output), Err(fmt::Error));
+}
+
+#[test]
+fn decode_to_slice() {
+    let mut output = [0u8; 4];
+    hex::decode_to_slice("6b697769", &mut output).unwrap();
+    assert_eq!(&output, b"kiwi");
+
+    assert_eq!(
+        hex::decode_to_slice("6b69776", &mut output),
+        Err(FromHexError::OddLength)
+    );
+}
+
+#[test]
+fn from_hex_array() {
+    assert_eq!(<[u8; 4]>::from_hex("6b697769"), Ok(*b"kiwi"));
+}